@@ -0,0 +1,296 @@
+//! Compares the crate's copy routines against `core::ptr::copy`,
+//! `core::ptr::copy_nonoverlapping`, and the `memcpy` from
+//! `compiler_builtins`. That crate can't be called directly from a bench (and
+//! on most hosts its `memcpy` isn't the one that gets linked), so a copy of its
+//! portable implementation is in the `compiler_builtins_memcpy` module below.
+//! That copy's byte loops are volatile, which makes the head and tail slower
+//! than the real crate's, so its results are only an approximation. They're
+//! labeled "(approx.)" in the output, and have `"approximation":true` in the
+//! JSON.
+//!
+//! Run with `cargo bench`. Each line reports the median time of one copy, and
+//! all of the results are also written as JSON to the path in the
+//! `COPY_BENCH_JSON` environment variable (by default `copy_bench.json` in
+//! Cargo's target tmp dir), so that runs can be compared over time.
+
+use aeabi_fns::{
+  copy_u16_backward, copy_u16_forward, copy_u32_backward, copy_u32_forward,
+  copy_u8_backward, copy_u8_forward,
+};
+use std::{hint::black_box, time::Instant};
+
+#[macro_use]
+#[path = "../tests/common/mod.rs"]
+mod common;
+use common::CopyFn;
+
+unsafe extern "C" fn ptr_copy(dest: *mut u8, src: *const u8, count: usize) {
+  core::ptr::copy(src, dest, count)
+}
+
+unsafe extern "C" fn ptr_copy_nonoverlapping(
+  dest: *mut u8, src: *const u8, count: usize,
+) {
+  core::ptr::copy_nonoverlapping(src, dest, count)
+}
+
+/// The `memcpy` from `compiler_builtins` (`src/mem/impls.rs`, version
+/// 0.1.160), as used on ARM targets. The `cold_path` hint is left out, since
+/// it's not stable, and the byte loops are volatile because this isn't a
+/// `no_builtins` crate, so LLVM would otherwise turn them back into calls to
+/// the linked `memcpy`. That makes the byte loops slower than in the real
+/// crate (which is `no_builtins`), so this is only an approximation of it.
+///
+/// compiler-builtins is licensed under MIT OR Apache-2.0.
+mod compiler_builtins_memcpy {
+  const WORD_SIZE: usize = core::mem::size_of::<usize>();
+  const WORD_MASK: usize = WORD_SIZE - 1;
+  const WORD_COPY_THRESHOLD: usize =
+    if 2 * WORD_SIZE > 16 { 2 * WORD_SIZE } else { 16 };
+
+  #[inline(always)]
+  unsafe fn load_chunk_aligned<T: Copy>(
+    src: *const usize, dst: *mut usize, load_sz: usize, offset: usize,
+  ) -> usize {
+    let chunk_sz = core::mem::size_of::<T>();
+    if (load_sz & chunk_sz) != 0 {
+      *dst.wrapping_byte_add(offset).cast::<T>() =
+        *src.wrapping_byte_add(offset).cast::<T>();
+      offset | chunk_sz
+    } else {
+      offset
+    }
+  }
+
+  #[inline(always)]
+  unsafe fn load_aligned_partial(src: *const usize, load_sz: usize) -> usize {
+    let mut i = 0;
+    let mut out = 0usize;
+    i = load_chunk_aligned::<u32>(src, &raw mut out, load_sz, i);
+    i = load_chunk_aligned::<u16>(src, &raw mut out, load_sz, i);
+    load_chunk_aligned::<u8>(src, &raw mut out, load_sz, i);
+    out
+  }
+
+  #[inline(always)]
+  unsafe fn load_aligned_end_partial(
+    src: *const usize, load_sz: usize,
+  ) -> usize {
+    let mut i = 0;
+    let mut out = 0usize;
+    let src_shifted = src.wrapping_byte_add(WORD_SIZE - load_sz);
+    let out_shifted = (&raw mut out).wrapping_byte_add(WORD_SIZE - load_sz);
+    i = load_chunk_aligned::<u8>(src_shifted, out_shifted, load_sz, i);
+    i = load_chunk_aligned::<u16>(src_shifted, out_shifted, load_sz, i);
+    load_chunk_aligned::<u32>(src_shifted, out_shifted, load_sz, i);
+    out
+  }
+
+  #[inline(always)]
+  unsafe fn copy_forward_bytes(
+    mut dest: *mut u8, mut src: *const u8, n: usize,
+  ) {
+    let dest_end = dest.wrapping_add(n);
+    while dest < dest_end {
+      dest.write_volatile(src.read_volatile());
+      dest = dest.wrapping_add(1);
+      src = src.wrapping_add(1);
+    }
+  }
+
+  #[inline(always)]
+  unsafe fn copy_forward_aligned_words(
+    dest: *mut u8, src: *const u8, n: usize,
+  ) {
+    let mut dest_usize = dest as *mut usize;
+    let mut src_usize = src as *mut usize;
+    let dest_end = dest.wrapping_add(n) as *mut usize;
+    while dest_usize < dest_end {
+      *dest_usize = *src_usize;
+      dest_usize = dest_usize.wrapping_add(1);
+      src_usize = src_usize.wrapping_add(1);
+    }
+  }
+
+  #[inline(always)]
+  unsafe fn copy_forward_misaligned_words(
+    dest: *mut u8, src: *const u8, n: usize,
+  ) {
+    let mut dest_usize = dest as *mut usize;
+    let dest_end = dest.wrapping_add(n) as *mut usize;
+    let offset = src as usize & WORD_MASK;
+    let shift = offset * 8;
+    let mut src_aligned = src.wrapping_byte_sub(offset) as *mut usize;
+    let mut prev_word =
+      load_aligned_end_partial(src_aligned, WORD_SIZE - offset);
+    while dest_usize.wrapping_add(1) < dest_end {
+      src_aligned = src_aligned.wrapping_add(1);
+      let cur_word = *src_aligned;
+      let reassembled = if cfg!(target_endian = "little") {
+        prev_word >> shift | cur_word << (WORD_SIZE * 8 - shift)
+      } else {
+        prev_word << shift | cur_word >> (WORD_SIZE * 8 - shift)
+      };
+      prev_word = cur_word;
+      *dest_usize = reassembled;
+      dest_usize = dest_usize.wrapping_add(1);
+    }
+    src_aligned = src_aligned.wrapping_add(1);
+    let cur_word = load_aligned_partial(src_aligned, offset);
+    let reassembled = if cfg!(target_endian = "little") {
+      prev_word >> shift | cur_word << (WORD_SIZE * 8 - shift)
+    } else {
+      prev_word << shift | cur_word >> (WORD_SIZE * 8 - shift)
+    };
+    *dest_usize = reassembled;
+  }
+
+  pub unsafe extern "C" fn memcpy(
+    mut dest: *mut u8, mut src: *const u8, mut n: usize,
+  ) {
+    if n >= WORD_COPY_THRESHOLD {
+      let dest_misalignment = (dest as usize).wrapping_neg() & WORD_MASK;
+      copy_forward_bytes(dest, src, dest_misalignment);
+      dest = dest.wrapping_add(dest_misalignment);
+      src = src.wrapping_add(dest_misalignment);
+      n -= dest_misalignment;
+
+      let n_words = n & !WORD_MASK;
+      let src_misalignment = src as usize & WORD_MASK;
+      if src_misalignment == 0 {
+        copy_forward_aligned_words(dest, src, n_words);
+      } else {
+        copy_forward_misaligned_words(dest, src, n_words);
+      }
+      dest = dest.wrapping_add(n_words);
+      src = src.wrapping_add(n_words);
+      n -= n_words;
+    }
+    copy_forward_bytes(dest, src, n);
+  }
+}
+
+/// Takes one-past-the-end pointers, like the crate's backward functions do.
+unsafe extern "C" fn ptr_copy_backward(
+  dest: *mut u8, src: *const u8, count: usize,
+) {
+  core::ptr::copy(src.sub(count), dest.sub(count), count)
+}
+
+const SIZES: &[usize] = &[16, 64, 256, 1024, 4096, 65536];
+
+/// `(dest, src)` offsets from a word-aligned address. For the backward
+/// functions these offset the one-past-the-end pointers instead. Functions
+/// skip the offsets that they don't support.
+const OFFSETS: &[(usize, usize)] = &[(0, 0), (2, 2), (0, 2), (1, 1), (1, 3)];
+
+const SAMPLES: usize = 51;
+
+struct Stats {
+  median: f64,
+  min: f64,
+  max: f64,
+}
+
+/// Gives the nanoseconds per call of `f`.
+fn measure(mut f: impl FnMut()) -> Stats {
+  // find an iteration count that takes at least about 1ms per sample
+  let mut iters = 1_u32;
+  loop {
+    let start = Instant::now();
+    for _ in 0..iters {
+      f();
+    }
+    if start.elapsed().as_micros() >= 1000 || iters >= (1 << 24) {
+      break;
+    }
+    iters *= 2;
+  }
+  let mut samples: Vec<f64> = (0..SAMPLES)
+    .map(|_| {
+      let start = Instant::now();
+      for _ in 0..iters {
+        f();
+      }
+      start.elapsed().as_nanos() as f64 / f64::from(iters)
+    })
+    .collect();
+  samples.sort_by(f64::total_cmp);
+  Stats {
+    median: samples[SAMPLES / 2],
+    min: samples[0],
+    max: samples[SAMPLES - 1],
+  }
+}
+
+/// The name of the [`compiler_builtins_memcpy`] results, which are only an
+/// approximation of the real crate.
+const APPROX_MEMCPY: &str = "compiler_builtins memcpy (approx.)";
+
+fn main() {
+  // words, so that the buffers are aligned for every routine
+  let src = vec![0xA5A5_A5A5_u32; 65536 / 4 + 1];
+  let mut dest = vec![0_u32; 65536 / 4 + 1];
+  let src = src.as_ptr().cast::<u8>();
+  let dest = dest.as_mut_ptr().cast::<u8>();
+
+  let forward: [(&str, CopyFn, usize); 6] = [
+    ("copy_u8_forward", as_copy_fn!(copy_u8_forward), 1),
+    ("copy_u16_forward", as_copy_fn!(copy_u16_forward), 2),
+    ("copy_u32_forward", as_copy_fn!(copy_u32_forward), 4),
+    ("core::ptr::copy", ptr_copy, 1),
+    ("core::ptr::copy_nonoverlapping", ptr_copy_nonoverlapping, 1),
+    (APPROX_MEMCPY, compiler_builtins_memcpy::memcpy, 1),
+  ];
+  let backward: [(&str, CopyFn, usize); 4] = [
+    ("copy_u8_backward", as_copy_fn!(copy_u8_backward), 1),
+    ("copy_u16_backward", as_copy_fn!(copy_u16_backward), 2),
+    ("copy_u32_backward", as_copy_fn!(copy_u32_backward), 4),
+    ("core::ptr::copy", ptr_copy_backward, 1),
+  ];
+
+  let mut json_entries = Vec::new();
+  for &size in SIZES {
+    for &(d, s) in OFFSETS {
+      for (direction, fns) in
+        [("forward", &forward[..]), ("backward", &backward)]
+      {
+        for &(name, f, align) in fns {
+          if d % align != 0 || s % align != 0 {
+            continue;
+          }
+          let stats = if direction == "forward" {
+            let (dest, src) = unsafe { (dest.add(d), src.add(s)) };
+            measure(|| unsafe {
+              f(black_box(dest), black_box(src), black_box(size))
+            })
+          } else {
+            let (dest, src) =
+              unsafe { (dest.add(d + size), src.add(s + size)) };
+            measure(|| unsafe {
+              f(black_box(dest), black_box(src), black_box(size))
+            })
+          };
+          let approximation = name == APPROX_MEMCPY;
+          println!(
+            "{direction:<8} {name:<34} {size:>6} bytes, offsets ({d},{s}): {:>10.1} ns",
+            stats.median
+          );
+          json_entries.push(format!(
+            "{{\"direction\":\"{direction}\",\"function\":\"{name}\",\
+            \"approximation\":{approximation},\"size\":{size},\
+            \"dest_offset\":{d},\"src_offset\":{s},\"median_ns\":{:.3},\"min_ns\":{:.3},\"max_ns\":{:.3}}}",
+            stats.median, stats.min, stats.max
+          ));
+        }
+      }
+    }
+  }
+
+  let path = std::env::var("COPY_BENCH_JSON").unwrap_or_else(|_| {
+    concat!(env!("CARGO_TARGET_TMPDIR"), "/copy_bench.json").to_string()
+  });
+  let json = format!("[\n  {}\n]\n", json_entries.join(",\n  "));
+  std::fs::write(&path, json).unwrap();
+  println!("results written to {path}");
+}
@@ -0,0 +1,92 @@
+//! Picks the `link_section` prefix for the crate's functions, and which groups
+//! of functions use it.
+//!
+//! The prefix comes from one of the placement features, or from the
+//! `AEABI_FNS_LINK_SECTION_PREFIX` environment variable for any other section
+//! naming convention. If more than one is given, the environment variable wins,
+//! and then the earliest feature in [`PLACEMENTS`], with a warning (features
+//! need to be additive, so this can't be an error).
+//!
+//! When there's a prefix, this sets the `link_section_prefix` cfg and passes
+//! the prefix along to the crate in an environment variable of the same name.
+//! It also sets a `place_<group>` cfg for each group that's picked with a group
+//! feature, or for just the copies if none are picked.
+
+use std::env;
+
+/// The placement features, and the section prefix that each one uses, in
+/// order of precedence.
+const PLACEMENTS: &[(&str, &str)] = &[
+  ("link_iwram", ".iwram."),
+  ("link_itcm", ".itcm."),
+  ("link_ramfunc", ".ramfunc."),
+  ("link_data", ".data."),
+];
+
+/// The groups of functions, each with a `place_<group>` feature and cfg.
+const GROUPS: &[&str] = &["copies", "compares", "strings", "fills", "decoders"];
+
+/// The groups that are placed when no group feature is picked. The others are
+/// large enough that they'd use up much of a small fast RAM, so they're only
+/// placed when asked for.
+const DEFAULT_GROUPS: &[&str] = &["copies"];
+
+const PREFIX_VAR: &str = "AEABI_FNS_LINK_SECTION_PREFIX";
+
+fn feature_enabled(feature: &str) -> bool {
+  let var = format!("CARGO_FEATURE_{}", feature.to_uppercase());
+  env::var_os(var).is_some()
+}
+
+fn main() {
+  println!("cargo:rustc-check-cfg=cfg(link_section_prefix)");
+  for group in GROUPS {
+    println!("cargo:rustc-check-cfg=cfg(place_{group})");
+  }
+  println!("cargo:rerun-if-env-changed={PREFIX_VAR}");
+
+  let mut prefixes: Vec<String> = Vec::new();
+  match env::var(PREFIX_VAR) {
+    Ok(prefix) if !prefix.is_empty() => prefixes.push(prefix),
+    _ => (),
+  }
+  prefixes.extend(
+    PLACEMENTS
+      .iter()
+      .filter(|(feature, _)| feature_enabled(feature))
+      .map(|(_, prefix)| prefix.to_string()),
+  );
+  let picked_groups: Vec<&str> = GROUPS
+    .iter()
+    .copied()
+    .filter(|group| feature_enabled(&format!("place_{group}")))
+    .collect();
+  match prefixes.as_slice() {
+    [] => {
+      if !picked_groups.is_empty() {
+        println!(
+          "cargo:warning=the `place_*` features have no effect without a \
+          placement feature or `{PREFIX_VAR}`"
+        );
+      }
+    }
+    [prefix, rest @ ..] => {
+      if !rest.is_empty() {
+        println!(
+          "cargo:warning=only one link section prefix can be used, so \
+          `{prefix}` was picked over {rest:?}"
+        );
+      }
+      println!("cargo:rustc-cfg=link_section_prefix");
+      println!("cargo:rustc-env={PREFIX_VAR}={prefix}");
+      let groups = if picked_groups.is_empty() {
+        DEFAULT_GROUPS
+      } else {
+        &picked_groups[..]
+      };
+      for group in groups {
+        println!("cargo:rustc-cfg=place_{group}");
+      }
+    }
+  }
+}
@@ -0,0 +1,79 @@
+//! Fuzzes every copy function against `core::ptr::copy`.
+//!
+//! Run with `cargo +nightly fuzz run copies` from the repository root.
+//!
+//! The input is adjusted to fit the chosen function's safety contract with the
+//! same helpers as `tests/proptest_copies.rs`.
+
+#![no_main]
+
+use aeabi_fns::{
+  copy_backward_any, copy_forward_any, copy_u16_backward, copy_u16_forward,
+  copy_u32_backward, copy_u32_forward, copy_u64_backward, copy_u64_forward,
+  copy_u8_backward, copy_u8_forward,
+};
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+
+#[macro_use]
+#[path = "../../tests/common/mod.rs"]
+mod common;
+use common::{fit_copy, run_copy, CopyFn};
+
+#[derive(Debug, Clone, Copy, Arbitrary)]
+enum Function {
+  U8Forward,
+  U8Backward,
+  U16Forward,
+  U16Backward,
+  U32Forward,
+  U32Backward,
+  U64Forward,
+  U64Backward,
+  AnyForward,
+  AnyBackward,
+}
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+  function: Function,
+  buf_len: u16,
+  len: u16,
+  s: u16,
+  d: u16,
+  fill: Vec<u8>,
+}
+
+fuzz_target!(|input: Input| {
+  let (f, forward, align): (CopyFn, bool, usize) = match input.function {
+    Function::U8Forward => (as_copy_fn!(copy_u8_forward), true, 1),
+    Function::U8Backward => (as_copy_fn!(copy_u8_backward), false, 1),
+    Function::U16Forward => (as_copy_fn!(copy_u16_forward), true, 2),
+    Function::U16Backward => (as_copy_fn!(copy_u16_backward), false, 2),
+    Function::U32Forward => (as_copy_fn!(copy_u32_forward), true, 4),
+    Function::U32Backward => (as_copy_fn!(copy_u32_backward), false, 4),
+    Function::U64Forward => (as_copy_fn!(copy_u64_forward), true, 8),
+    Function::U64Backward => (as_copy_fn!(copy_u64_backward), false, 8),
+    Function::AnyForward => (as_copy_fn!(copy_forward_any), true, 1),
+    Function::AnyBackward => (as_copy_fn!(copy_backward_any), false, 1),
+  };
+
+  // keep the buffers small enough that each run is quick
+  let buf_len = usize::from(input.buf_len % 4096);
+  let (len, s, d) =
+    (usize::from(input.len), usize::from(input.s), usize::from(input.d));
+  let copy = fit_copy(buf_len, len, s, d, forward, align);
+
+  let mut base = vec![0_u64; copy.buf_len.div_ceil(8)];
+  for (b, x) in as_bytes_mut(&mut base).iter_mut().zip(input.fill.iter()) {
+    *b = *x;
+  }
+  let (expected, actual) = run_copy(f, forward, copy, base);
+  assert_eq!(expected, actual, "{:?} {copy:?}", input.function);
+});
+
+fn as_bytes_mut(words: &mut [u64]) -> &mut [u8] {
+  unsafe {
+    core::slice::from_raw_parts_mut(words.as_mut_ptr().cast(), words.len() * 8)
+  }
+}
@@ -0,0 +1,27 @@
+#[cfg(feature = "c_symbols")]
+use crate::*;
+
+/// The C `bcmp` function (exported under that name with the `c_symbols`
+/// feature).
+///
+/// This only has to report zero or non-zero, so it's the same function as
+/// [`memcmp`](crate::memcmp), and shares its code.
+///
+/// ## Safety
+/// * The same as [`compare_u8`](crate::compare_u8).
+#[cfg(not(feature = "c_symbols"))]
+pub use crate::memcmp::memcmp as bcmp;
+
+/// The C `bcmp` function (exported under that name with the `c_symbols`
+/// feature).
+///
+/// This only has to report zero or non-zero, so it just calls [`memcmp`]. That
+/// is never inlined with `c_symbols`, so this is only a jump to its code.
+///
+/// ## Safety
+/// * The same as [`compare_u8`].
+#[cfg(feature = "c_symbols")]
+#[no_mangle]
+pub unsafe extern "C" fn bcmp(a: *const u8, b: *const u8, count: usize) -> i32 {
+  memcmp(a, b, count)
+}
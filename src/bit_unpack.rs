@@ -0,0 +1,91 @@
+/// The settings for [`bit_unpack`], like the BIOS's `UnPackInfo`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitUnpackInfo {
+  /// The width of each source unit in bits: 1, 2, 4, or 8.
+  pub src_bits: u8,
+  /// The width of each output unit in bits: 1, 2, 4, 8, 16, or 32.
+  pub dest_bits: u8,
+  /// Added to each source unit. Only the low 31 bits are used, since the BIOS
+  /// keeps the `offset_zeros` flag in bit 31.
+  pub offset: u32,
+  /// If `offset` is also added to units that are zero. Otherwise zero units
+  /// stay zero, which keeps them transparent.
+  pub offset_zeros: bool,
+}
+
+/// Expands each `src_bits` wide unit of `src` into a `dest_bits` wide unit of
+/// `dest` (like the BIOS's `BitUnPack`), and gives the number of words
+/// written.
+///
+/// Units are taken from each byte of `src` starting at the low bits, and are
+/// packed into each output word starting at the low bits too. So for example a
+/// 1bpp font byte becomes one word of 4bpp pixels, with the leftmost pixel (bit
+/// 0) in the low 4 bits.
+///
+/// Each unit that's non-zero (or every unit, with `offset_zeros`) has `offset`
+/// added to it. Like the BIOS, the sum isn't cut down to `dest_bits`, so any
+/// bits above that are OR-ed into the next units of the same word (and bits
+/// past the top of the word are lost).
+///
+/// The output is built up in a register and stored a word at a time, so
+/// `dest` can be VRAM. Also like the BIOS, only whole words are stored: if the
+/// output isn't a whole number of words, the units that would only partly fill
+/// the last word are dropped.
+///
+/// ## Panics
+/// * If `src_bits` or `dest_bits` isn't one of the widths allowed.
+/// * If `dest` is shorter than the output, which is `src.len() * 8 / src_bits *
+///   dest_bits` bits, rounded down to whole words.
+#[inline]
+#[cfg_attr(place_decoders, link_section = section_name!("bit_unpack"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub fn bit_unpack(src: &[u8], dest: &mut [u32], info: BitUnpackInfo) -> usize {
+  let BitUnpackInfo { src_bits, dest_bits, offset, offset_zeros } = info;
+  assert!(
+    matches!(src_bits, 1 | 2 | 4 | 8),
+    "bit_unpack: `src_bits` must be 1, 2, 4, or 8"
+  );
+  assert!(
+    matches!(dest_bits, 1 | 2 | 4 | 8 | 16 | 32),
+    "bit_unpack: `dest_bits` must be 1, 2, 4, 8, 16, or 32"
+  );
+  let (src_bits, dest_bits) = (u32::from(src_bits), u32::from(dest_bits));
+  // The widths are powers of two, so shifts avoid a division (which ARMv4T
+  // would need a function call for).
+  let units = (src.len() * 8) >> src_bits.trailing_zeros();
+  let words = (units << dest_bits.trailing_zeros()) / 32;
+  assert!(
+    dest.len() >= words,
+    "bit_unpack: `dest` needs room for {words} words"
+  );
+  let src_mask = (1 << src_bits) - 1;
+  let offset = offset & 0x7FFF_FFFF;
+  let mut out = dest.as_mut_ptr();
+  let (mut word, mut filled) = (0_u32, 0);
+  for &byte in src {
+    let mut byte = u32::from(byte);
+    for _ in 0..(8 >> src_bits.trailing_zeros()) {
+      let unit = byte & src_mask;
+      byte >>= src_bits;
+      if unit != 0 || offset_zeros {
+        // can't overflow: `unit` is at most 255, and `offset` is under 2^31
+        word |= (unit + offset) << filled;
+      }
+      filled += dest_bits;
+      if filled == 32 {
+        // Safety: there are `words` words of `dest`, and each one is only
+        // written once. Volatile so that each word is one store, and for the
+        // same reason as in `copy_u8_forward_advance`.
+        unsafe {
+          out.write_volatile(word);
+          out = out.add(1);
+        }
+        (word, filled) = (0, 0);
+      }
+    }
+  }
+  words
+}
@@ -0,0 +1,81 @@
+use crate::*;
+
+/// Compares `count` bytes at `a` and `b`, going upward in address value.
+///
+/// Comparisons are done in 2-byte chunks as much as possible. When a chunk
+/// differs, or if the number of bytes is odd, the final part is compared
+/// one byte at a time. The return value is the same as with [`compare_u8`].
+///
+/// ## Safety
+/// * If `count` is zero, the `a` and `b` pointers are not accessed, and they
+///   can even be invalid or null.
+/// * If `count` is non-zero, then both `a` and `b` must be aligned, and valid
+///   for `count` initialized bytes forward. The regions are allowed to overlap.
+/// * `count` may not exceed `isize::MAX as usize`. (All Rust allocations
+///   already follow this rule, but perhaps it's worth stating that it is an
+///   assumption of the function.)
+#[inline]
+#[cfg_attr(place_compares, link_section = section_name!("compare_u16"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub unsafe extern "C" fn compare_u16(
+  mut a: *const u16, mut b: *const u16, mut count: usize,
+) -> i32 {
+  if count > 0 {
+    debug_assert!((a as usize).is_multiple_of(2), "a must be aligned to 2!");
+    debug_assert!((b as usize).is_multiple_of(2), "b must be aligned to 2!");
+  }
+  cfg_armv4t! {
+    yes: {
+      let diff: i32;
+      core::arch::asm! {
+        "1:",
+        "subs    {count}, {count}, #2",
+        "blt     2f",
+        "ldrh    {ta}, [{a}], #2",
+        "ldrh    {tb}, [{b}], #2",
+        "cmp     {ta}, {tb}",
+        "beq     1b",
+        // The halfwords differ, so back up and find the byte that differs. The
+        // count is set so that there's 2 bytes left after the `add` below.
+        "sub     {a}, {a}, #2",
+        "sub     {b}, {b}, #2",
+        "mov     {count}, #0",
+        "2:",
+        "add     {count}, {count}, #2",
+        // This part is the same as `compare_u8`
+        "3:",
+        "subs    {count}, {count}, #1",
+        "movlt   {ta}, #0",
+        "movlt   {tb}, #0",
+        "ldrbge  {ta}, [{a}], #1",
+        "ldrbge  {tb}, [{b}], #1",
+        "cmpge   {ta}, {tb}",
+        "beq     3b",
+        "sub     {ta}, {ta}, {tb}",
+        a = inout(reg) a => _,
+        b = inout(reg) b => _,
+        count = inout(reg) count => _,
+        ta = out(reg) diff,
+        tb = out(reg) _,
+        options(nostack, readonly)
+      }
+      diff
+    }
+    no: {
+      while count >= 2 {
+        if *a != *b {
+          break;
+        }
+        a = a.add(1);
+        b = b.add(1);
+        count -= 2;
+      }
+      // Either there's a differing halfword here, or there's at most 1 byte
+      // left to check.
+      compare_u8(a.cast(), b.cast(), count.min(2))
+    }
+  }
+}
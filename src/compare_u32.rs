@@ -0,0 +1,80 @@
+use crate::*;
+
+/// Compares `count` bytes at `a` and `b`, going upward in address value.
+///
+/// Comparisons are done in 4-byte chunks as much as possible. When a chunk
+/// differs, or if the number of bytes is not a multiple of 4, the final part is
+/// compared one byte at a time. The return value is the same as with
+/// [`compare_u8`].
+///
+/// ## Safety
+/// * If `count` is zero, the `a` and `b` pointers are not accessed, and they
+///   can even be invalid or null.
+/// * If `count` is non-zero, then both `a` and `b` must be aligned, and valid
+///   for `count` initialized bytes forward. The regions are allowed to overlap.
+/// * `count` may not exceed `isize::MAX as usize`. (All Rust allocations
+///   already follow this rule, but perhaps it's worth stating that it is an
+///   assumption of the function.)
+#[inline]
+#[cfg_attr(place_compares, link_section = section_name!("compare_u32"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub unsafe extern "C" fn compare_u32(
+  mut a: *const u32, mut b: *const u32, mut count: usize,
+) -> i32 {
+  if count > 0 {
+    debug_assert!((a as usize).is_multiple_of(4), "a must be aligned to 4!");
+    debug_assert!((b as usize).is_multiple_of(4), "b must be aligned to 4!");
+  }
+  cfg_armv4t! {
+    yes: {
+      let diff: i32;
+      core::arch::asm! {
+        // The loop reasoning here is similar to `compare_u16`
+        "1:",
+        "subs    {count}, {count}, #4",
+        "blt     2f",
+        "ldr     {ta}, [{a}], #4",
+        "ldr     {tb}, [{b}], #4",
+        "cmp     {ta}, {tb}",
+        "beq     1b",
+        "sub     {a}, {a}, #4",
+        "sub     {b}, {b}, #4",
+        "mov     {count}, #0",
+        "2:",
+        "add     {count}, {count}, #4",
+        "3:",
+        "subs    {count}, {count}, #1",
+        "movlt   {ta}, #0",
+        "movlt   {tb}, #0",
+        "ldrbge  {ta}, [{a}], #1",
+        "ldrbge  {tb}, [{b}], #1",
+        "cmpge   {ta}, {tb}",
+        "beq     3b",
+        "sub     {ta}, {ta}, {tb}",
+        a = inout(reg) a => _,
+        b = inout(reg) b => _,
+        count = inout(reg) count => _,
+        ta = out(reg) diff,
+        tb = out(reg) _,
+        options(nostack, readonly)
+      }
+      diff
+    }
+    no: {
+      while count >= 4 {
+        if *a != *b {
+          break;
+        }
+        a = a.add(1);
+        b = b.add(1);
+        count -= 4;
+      }
+      // Either there's a differing word here, or there's at most 3 bytes left
+      // to check.
+      compare_u8(a.cast(), b.cast(), count.min(4))
+    }
+  }
+}
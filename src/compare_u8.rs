@@ -0,0 +1,62 @@
+/// Compares `count` bytes at `a` and `b`, going upward in address value.
+///
+/// The return value follows the rules of C's `memcmp`: it's zero if the regions
+/// are equal, otherwise it's the first byte that differs in `a` minus the byte
+/// at the same position in `b` (each as an unsigned value).
+///
+/// ## Safety
+/// * If `count` is zero, the `a` and `b` pointers are not accessed, and they
+///   can even be invalid or null.
+/// * If `count` is non-zero, then both `a` and `b` must be valid for `count`
+///   initialized bytes forward. The regions are allowed to overlap.
+/// * `count` may not exceed `isize::MAX as usize`. (All Rust allocations
+///   already follow this rule, but perhaps it's worth stating that it is an
+///   assumption of the function.)
+#[inline]
+#[cfg_attr(place_compares, link_section = section_name!("compare_u8"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub unsafe extern "C" fn compare_u8(
+  mut a: *const u8, mut b: *const u8, mut count: usize,
+) -> i32 {
+  cfg_armv4t! {
+    yes: {
+      let diff: i32;
+      core::arch::asm! {
+        "1:",
+        "subs    {count}, {count}, #1",
+        // if we've run out of bytes, the regions were equal
+        "movlt   {ta}, #0",
+        "movlt   {tb}, #0",
+        "ldrbge  {ta}, [{a}], #1",
+        "ldrbge  {tb}, [{b}], #1",
+        "cmpge   {ta}, {tb}",
+        // We only go again if we did a compare and it was equal. When the count
+        // runs out, `subs` gave a non-zero value so this falls through.
+        "beq     1b",
+        "sub     {ta}, {ta}, {tb}",
+        a = inout(reg) a => _,
+        b = inout(reg) b => _,
+        count = inout(reg) count => _,
+        ta = out(reg) diff,
+        tb = out(reg) _,
+        options(nostack, readonly)
+      }
+      diff
+    }
+    no: {
+      while count >= 1 {
+        let (x, y) = (*a, *b);
+        if x != y {
+          return i32::from(x) - i32::from(y);
+        }
+        a = a.add(1);
+        b = b.add(1);
+        count -= 1;
+      }
+      0
+    }
+  }
+}
@@ -0,0 +1,254 @@
+//! Checks of the copy and swap functions' safety contracts.
+//!
+//! The `validate_*` functions give the first broken rule as a [`CopyError`],
+//! for the `try_copy_*` functions. The `check_*` functions panic with a message
+//! naming the broken rule instead, and only check anything with
+//! `debug_assertions` or the `checked` feature.
+
+use crate::*;
+
+/// Checks the parts of a forward copy function's safety contract that can be
+/// checked from the addresses alone.
+///
+/// `count` is in bytes, and `align` is the alignment the function needs.
+#[inline(always)]
+pub(crate) const fn validate_copy_forward(
+  dest: usize, src: usize, count: usize, align: usize,
+) -> Result<(), CopyError> {
+  if count == 0 {
+    return Ok(());
+  }
+  if let Err(e) = validate_common(dest, src, count, align) {
+    return Err(e);
+  }
+  if dest.checked_add(count).is_none() || src.checked_add(count).is_none() {
+    return Err(CopyError::LengthOverflow);
+  }
+  if src < dest && src + count > dest {
+    return Err(CopyError::BadOverlap);
+  }
+  Ok(())
+}
+
+/// Like [`validate_copy_forward`], but `dest` and `src` are one-past-the-end
+/// addresses.
+#[inline(always)]
+pub(crate) const fn validate_copy_backward(
+  dest: usize, src: usize, count: usize, align: usize,
+) -> Result<(), CopyError> {
+  if count == 0 {
+    return Ok(());
+  }
+  if let Err(e) = validate_common(dest, src, count, align) {
+    return Err(e);
+  }
+  if dest < count || src < count {
+    return Err(CopyError::LengthOverflow);
+  }
+  if src > dest && src - count < dest {
+    return Err(CopyError::BadOverlap);
+  }
+  Ok(())
+}
+
+/// The rules that are the same for both directions.
+#[inline(always)]
+const fn validate_common(
+  dest: usize, src: usize, count: usize, align: usize,
+) -> Result<(), CopyError> {
+  if dest == 0 || src == 0 {
+    Err(CopyError::Null)
+  } else if !dest.is_multiple_of(align) {
+    Err(CopyError::Misaligned { which: CopyPointer::Dest, required: align })
+  } else if !src.is_multiple_of(align) {
+    Err(CopyError::Misaligned { which: CopyPointer::Src, required: align })
+  } else if count > isize::MAX as usize {
+    Err(CopyError::LengthOverflow)
+  } else {
+    Ok(())
+  }
+}
+
+/// Panics if the arguments to a forward copy function break its safety
+/// contract, with a message naming the function (`name`) and the broken rule.
+///
+/// This only checks anything with `debug_assertions` or the `checked` feature.
+/// Since the copy functions are `extern "C"`, a panic will abort.
+#[inline(always)]
+pub(crate) fn check_copy_forward(
+  name: &str, dest: usize, src: usize, count: usize, align: usize,
+) {
+  if !cfg!(any(debug_assertions, feature = "checked")) {
+    return;
+  }
+  if let Err(e) = validate_copy_forward(dest, src, count, align) {
+    contract_panic(name, e, dest, src, count, true);
+  }
+}
+
+/// Panics if `count` isn't a whole number of `size` byte elements, for the
+/// copies that rearrange the bytes of each element. Like
+/// [`check_copy_forward`], this only checks anything with `debug_assertions`
+/// or the `checked` feature.
+#[inline(always)]
+pub(crate) fn check_whole_elements(name: &str, count: usize, size: usize) {
+  if cfg!(any(debug_assertions, feature = "checked"))
+    && !count.is_multiple_of(size)
+  {
+    whole_elements_panic(name, count, size);
+  }
+}
+
+#[cold]
+#[inline(never)]
+fn whole_elements_panic(name: &str, count: usize, size: usize) -> ! {
+  panic!("{name}: `count` ({count}) must be a multiple of {size}")
+}
+
+/// Like [`check_copy_forward`], but `dest` and `src` are one-past-the-end
+/// pointers.
+#[inline(always)]
+pub(crate) fn check_copy_backward(
+  name: &str, dest: usize, src: usize, count: usize, align: usize,
+) {
+  if !cfg!(any(debug_assertions, feature = "checked")) {
+    return;
+  }
+  if let Err(e) = validate_copy_backward(dest, src, count, align) {
+    contract_panic(name, e, dest, src, count, false);
+  }
+}
+
+/// Gives the size in bytes of `count` values of `T`, for the generic copies.
+///
+/// Unlike the other contract checks this always happens, since the copy
+/// function that gets the size can't tell that it wrapped. An overflow is
+/// reported with the size saturated at `usize::MAX`, which is always over the
+/// `isize::MAX` limit.
+#[inline(always)]
+pub(crate) fn byte_count<T>(
+  name: &str, dest: usize, src: usize, count: usize, forward: bool,
+) -> usize {
+  match count.checked_mul(core::mem::size_of::<T>()) {
+    Some(bytes) => bytes,
+    None => contract_panic(
+      name,
+      CopyError::LengthOverflow,
+      dest,
+      src,
+      usize::MAX,
+      forward,
+    ),
+  }
+}
+
+/// Panics with the message for a broken rule. The arguments are the same as
+/// [`check_copy_forward`], and `forward` is the direction of the copy.
+#[cold]
+#[inline(never)]
+fn contract_panic(
+  name: &str, e: CopyError, dest: usize, src: usize, count: usize,
+  forward: bool,
+) -> ! {
+  match e {
+    CopyError::Null => panic!(
+      "{name}: `dest` ({dest:#x}) and `src` ({src:#x}) must not be null when \
+      `count` ({count}) is non-zero"
+    ),
+    CopyError::Misaligned { which: CopyPointer::Dest, required } => {
+      panic!("{name}: `dest` ({dest:#x}) must be aligned to {required}")
+    }
+    CopyError::Misaligned { which: CopyPointer::Src, required } => {
+      panic!("{name}: `src` ({src:#x}) must be aligned to {required}")
+    }
+    CopyError::LengthOverflow if count > isize::MAX as usize => {
+      panic!("{name}: `count` ({count}) may not exceed `isize::MAX`")
+    }
+    CopyError::LengthOverflow if forward => panic!(
+      "{name}: `dest` ({dest:#x}) and `src` ({src:#x}) must each be valid for \
+      `count` ({count}) bytes forward, but that goes past the end of memory"
+    ),
+    CopyError::LengthOverflow => panic!(
+      "{name}: `dest` ({dest:#x}) and `src` ({src:#x}) must be \
+      one-past-the-end pointers for `count` ({count}) bytes backward, but that \
+      goes below address zero"
+    ),
+    CopyError::BadOverlap if forward => panic!(
+      "{name}: when the regions overlap `src` ({src:#x}) must not be less than \
+      `dest` ({dest:#x}), since a forward copy would overwrite `src` before \
+      reading it (use a backward copy instead)"
+    ),
+    CopyError::BadOverlap => panic!(
+      "{name}: when the regions overlap `src` ({src:#x}) must not be greater \
+      than `dest` ({dest:#x}), since a backward copy would overwrite `src` \
+      before reading it (use a forward copy instead)"
+    ),
+  }
+}
+
+/// Checks the parts of a swap function's safety contract that can be checked
+/// from the addresses alone, like [`validate_copy_forward`] does for copies.
+///
+/// `a` and `b` are reported as the `Dest` and `Src` pointers, and any overlap
+/// (other than `a` equal to `b`) is a [`CopyError::BadOverlap`].
+#[inline(always)]
+const fn validate_swap(
+  a: usize, b: usize, count: usize, align: usize,
+) -> Result<(), CopyError> {
+  if count == 0 {
+    return Ok(());
+  }
+  if let Err(e) = validate_common(a, b, count, align) {
+    return Err(e);
+  }
+  if a.checked_add(count).is_none() || b.checked_add(count).is_none() {
+    return Err(CopyError::LengthOverflow);
+  }
+  if a != b && a < b + count && b < a + count {
+    return Err(CopyError::BadOverlap);
+  }
+  Ok(())
+}
+
+/// Panics if the arguments to a swap function break its safety contract, like
+/// [`check_copy_forward`] does for copies.
+#[inline(always)]
+pub(crate) fn check_swap(
+  name: &str, a: usize, b: usize, count: usize, align: usize,
+) {
+  if !cfg!(any(debug_assertions, feature = "checked")) {
+    return;
+  }
+  if let Err(e) = validate_swap(a, b, count, align) {
+    swap_panic(name, e, a, b, count, align);
+  }
+}
+
+/// Panics with the message for a broken rule of a swap. The arguments are the
+/// same as [`check_swap`].
+#[cold]
+#[inline(never)]
+fn swap_panic(
+  name: &str, e: CopyError, a: usize, b: usize, count: usize, align: usize,
+) -> ! {
+  match e {
+    CopyError::Null => panic!(
+      "{name}: `a` ({a:#x}) and `b` ({b:#x}) must not be null when `count` \
+      ({count}) is non-zero"
+    ),
+    CopyError::Misaligned { .. } => {
+      panic!("{name}: `a` ({a:#x}) and `b` ({b:#x}) must be aligned to {align}")
+    }
+    CopyError::LengthOverflow if count > isize::MAX as usize => {
+      panic!("{name}: `count` ({count}) may not exceed `isize::MAX`")
+    }
+    CopyError::LengthOverflow => panic!(
+      "{name}: `a` ({a:#x}) and `b` ({b:#x}) must each be valid for `count` \
+      ({count}) bytes forward, but that goes past the end of memory"
+    ),
+    CopyError::BadOverlap => panic!(
+      "{name}: the regions at `a` ({a:#x}) and `b` ({b:#x}) must not overlap \
+      (unless `a` equals `b`), but they're within `count` ({count}) bytes"
+    ),
+  }
+}
@@ -0,0 +1,39 @@
+use crate::*;
+
+/// Copies `count` values of type `T` from `src` to `dest`, starting at
+/// one-past-the-end.
+///
+/// This picks the widest copy function that `T`'s alignment allows
+/// ([`copy_u64_backward`], [`copy_u32_backward`], [`copy_u16_backward`], or
+/// [`copy_u8_backward`]) at compile time, so it becomes a direct call to that
+/// function.
+///
+/// ## Safety
+/// * If `count` is zero or `T` is zero-sized, the `src` and `dest` pointers are
+///   not accessed, and they can even be invalid or null.
+/// * Otherwise, both `src` and `dest` must be aligned for `T`, be the
+///   one-past-the-end pointers for `count` values of `T` backward, and one of
+///   the following must be true:
+///   * The `src` and `dest` regions are entirely disjoint.
+///   * `src` equals `dest` (there is exact overlap).
+///   * `src` is *less* than `dest` (a partial overlap).
+/// * The total size, `count * size_of::<T>()`, may not exceed `isize::MAX as
+///   usize`.
+///
+/// ## Panics
+/// * If `count * size_of::<T>()` overflows a `usize`. This is checked even
+///   without `debug_assertions` or the `checked` feature.
+#[inline]
+pub unsafe fn copy_backward<T>(dest: *mut T, src: *const T, count: usize) {
+  let bytes =
+    byte_count::<T>("copy_backward", dest as usize, src as usize, count, false);
+  if core::mem::align_of::<T>() >= 8 {
+    copy_u64_backward(dest.cast(), src.cast(), bytes)
+  } else if core::mem::align_of::<T>() == 4 {
+    copy_u32_backward(dest.cast(), src.cast(), bytes)
+  } else if core::mem::align_of::<T>() == 2 {
+    copy_u16_backward(dest.cast(), src.cast(), bytes)
+  } else {
+    copy_u8_backward(dest.cast(), src.cast(), bytes)
+  }
+}
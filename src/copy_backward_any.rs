@@ -0,0 +1,43 @@
+use crate::*;
+
+/// Copies `count` bytes from `src` to `dest`, starting at one-past-the-end,
+/// with any alignment.
+///
+/// This picks [`copy_u32_backward`], [`copy_u16_backward`], or
+/// [`copy_u8_backward`] based on the alignment of both pointers. When the
+/// pointers are misaligned by the same amount, a few bytes are copied first so
+/// that the rest can use the wider function.
+///
+/// ## Safety
+/// * The same as [`copy_u8_backward`].
+#[inline]
+#[cfg_attr(place_copies, link_section = section_name!("copy_backward_any"))]
+pub unsafe extern "C" fn copy_backward_any(
+  mut dest: *mut mu_u8, mut src: *const mu_u8, mut count: usize,
+) {
+  check_copy_backward(
+    "copy_backward_any",
+    dest as usize,
+    src as usize,
+    count,
+    1,
+  );
+  let misalignment = (dest as usize) ^ (src as usize);
+  if misalignment & 0b11 == 0 {
+    let prologue = ((dest as usize) & 0b11).min(count);
+    copy_u8_backward(dest, src, prologue);
+    dest = dest.sub(prologue);
+    src = src.sub(prologue);
+    count -= prologue;
+    copy_u32_backward(dest.cast(), src.cast(), count)
+  } else if misalignment & 0b1 == 0 {
+    let prologue = ((dest as usize) & 0b1).min(count);
+    copy_u8_backward(dest, src, prologue);
+    dest = dest.sub(prologue);
+    src = src.sub(prologue);
+    count -= prologue;
+    copy_u16_backward(dest.cast(), src.cast(), count)
+  } else {
+    copy_u8_backward(dest, src, count)
+  }
+}
@@ -0,0 +1,55 @@
+/// Why one of the `try_copy_*` functions, such as
+/// [`try_copy_forward`](crate::try_copy_forward), refused to copy.
+///
+/// Each variant is a rule of the copy functions' safety contract that the
+/// arguments broke.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CopyError {
+  /// A pointer wasn't aligned to `required` bytes.
+  Misaligned {
+    /// The pointer that was misaligned.
+    which: CopyPointer,
+    /// The alignment the copy needs.
+    required: usize,
+  },
+  /// The regions overlap in the way that the copy's direction can't handle:
+  /// `src` is below `dest` for a forward copy, or above `dest` for a backward
+  /// copy.
+  BadOverlap,
+  /// The size in bytes is more than `isize::MAX`, or one of the regions
+  /// doesn't fit in the address space.
+  LengthOverflow,
+  /// A pointer was null while there were bytes to copy.
+  Null,
+}
+
+/// One of the two pointers given to a copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CopyPointer {
+  /// The `dest` pointer.
+  Dest,
+  /// The `src` pointer.
+  Src,
+}
+
+impl core::fmt::Display for CopyError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      CopyError::Misaligned { which: CopyPointer::Dest, required } => {
+        write!(f, "`dest` must be aligned to {required}")
+      }
+      CopyError::Misaligned { which: CopyPointer::Src, required } => {
+        write!(f, "`src` must be aligned to {required}")
+      }
+      CopyError::BadOverlap => {
+        f.write_str("the regions overlap in the wrong direction for the copy")
+      }
+      CopyError::LengthOverflow => {
+        f.write_str("the copy doesn't fit in the address space")
+      }
+      CopyError::Null => f.write_str("a pointer is null"),
+    }
+  }
+}
+
+impl core::error::Error for CopyError {}
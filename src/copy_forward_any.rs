@@ -0,0 +1,37 @@
+use crate::*;
+
+/// Copies `count` bytes from `src` to `dest`, going upward in address value,
+/// with any alignment.
+///
+/// This picks [`copy_u32_forward`], [`copy_u16_forward`], or
+/// [`copy_u8_forward`] based on the alignment of both pointers. When the
+/// pointers are misaligned by the same amount, a few bytes are copied first so
+/// that the rest can use the wider function.
+///
+/// ## Safety
+/// * The same as [`copy_u8_forward`].
+#[inline]
+#[cfg_attr(place_copies, link_section = section_name!("copy_forward_any"))]
+pub unsafe extern "C" fn copy_forward_any(
+  mut dest: *mut mu_u8, mut src: *const mu_u8, mut count: usize,
+) {
+  check_copy_forward("copy_forward_any", dest as usize, src as usize, count, 1);
+  let misalignment = (dest as usize) ^ (src as usize);
+  if misalignment & 0b11 == 0 {
+    let prologue = ((dest as usize).wrapping_neg() & 0b11).min(count);
+    copy_u8_forward(dest, src, prologue);
+    dest = dest.add(prologue);
+    src = src.add(prologue);
+    count -= prologue;
+    copy_u32_forward(dest.cast(), src.cast(), count)
+  } else if misalignment & 0b1 == 0 {
+    let prologue = ((dest as usize) & 0b1).min(count);
+    copy_u8_forward(dest, src, prologue);
+    dest = dest.add(prologue);
+    src = src.add(prologue);
+    count -= prologue;
+    copy_u16_forward(dest.cast(), src.cast(), count)
+  } else {
+    copy_u8_forward(dest, src, count)
+  }
+}
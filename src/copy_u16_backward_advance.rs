@@ -0,0 +1,91 @@
+use crate::*;
+
+/// Like [`copy_u16_backward`], but returns the `dest` and `src` pointers moved
+/// back by `count` bytes, which are the one-past-the-end pointers for the next
+/// backward copy.
+///
+/// ## Safety
+/// * The same as [`copy_u16_backward`].
+#[inline]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub unsafe fn copy_u16_backward_advance(
+  dest: *mut mu_u16, src: *const mu_u16, count: usize,
+) -> (*mut mu_u16, *const mu_u16) {
+  check_copy_backward(
+    "copy_u16_backward_advance",
+    dest as usize,
+    src as usize,
+    count,
+    2,
+  );
+  copy_u16_backward_unchecked(dest, src, count)
+}
+
+/// The body of [`copy_u16_backward_advance`] without the contract check, shared
+/// with [`copy_u16_backward`] so that the check only happens once.
+#[inline(always)]
+pub(crate) unsafe fn copy_u16_backward_unchecked(
+  mut dest: *mut mu_u16, mut src: *const mu_u16, mut count: usize,
+) -> (*mut mu_u16, *const mu_u16) {
+  // IMPORTANT: in the backward loop we adjust the pointers *before* the copy,
+  // instead of after the copy like the forward loop does.
+  cfg_armv4t! {
+    yes: {
+      // The loop reasoning here is similar to `copy_u8_backward_advance`
+      core::arch::asm! {
+        "1:",
+        "subs    {count}, {count}, #2",
+        "ldrhge  {temp}, [{src}, #-2]!",
+        "strhge  {temp}, [{dest}, #-2]!",
+        "bgt     1b",
+        dest = inout(reg) dest,
+        src = inout(reg) src,
+        count = inout(reg) count,
+        temp = out(reg) _,
+        options(nostack)
+      }
+    }
+    no: {
+      cfg_thumb2! {
+        yes: {
+          // Same as the `armv4t` loop, but with an IT block.
+          core::arch::asm! {
+            "1:",
+            "subs    {count}, {count}, #2",
+            "itt     ge",
+            "ldrhge  {temp}, [{src}, #-2]!",
+            "strhge  {temp}, [{dest}, #-2]!",
+            "bgt     1b",
+            dest = inout(reg) dest,
+            src = inout(reg) src,
+            count = inout(reg) count,
+            temp = out(reg) _,
+            options(nostack)
+          }
+        }
+        no: {
+          #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+          simd128_bulk_backward!(dest, src, count);
+          // volatile for the same reason as in `copy_u8_forward_advance`
+          while count >= 2 {
+            dest = dest.sub(1);
+            src = src.sub(1);
+            dest.write_volatile(src.read_volatile());
+            count -= 2;
+          }
+        }
+      }
+    }
+  }
+  // The ASM loop will always underflow the `count` value, so we do a bit test
+  // to check to test for when there's a 1-byte copy at the end.
+  if (count & 1) != 0 {
+    dest = dest.byte_sub(1);
+    src = src.byte_sub(1);
+    *dest.cast::<mu_u8>() = *src.cast::<mu_u8>();
+  }
+  (dest, src)
+}
@@ -0,0 +1,89 @@
+use crate::*;
+
+/// Like [`copy_u16_forward`], but returns the `dest` and `src` pointers
+/// advanced by `count` bytes, which is where the next forward copy would
+/// continue from.
+///
+/// ## Safety
+/// * The same as [`copy_u16_forward`].
+#[inline]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub unsafe fn copy_u16_forward_advance(
+  dest: *mut mu_u16, src: *const mu_u16, count: usize,
+) -> (*mut mu_u16, *const mu_u16) {
+  check_copy_forward(
+    "copy_u16_forward_advance",
+    dest as usize,
+    src as usize,
+    count,
+    2,
+  );
+  copy_u16_forward_unchecked(dest, src, count)
+}
+
+/// The body of [`copy_u16_forward_advance`] without the contract check, shared
+/// with [`copy_u16_forward`] so that the check only happens once.
+#[inline(always)]
+pub(crate) unsafe fn copy_u16_forward_unchecked(
+  mut dest: *mut mu_u16, mut src: *const mu_u16, mut count: usize,
+) -> (*mut mu_u16, *const mu_u16) {
+  cfg_armv4t! {
+    yes: {
+      // The loop reasoning here is similar to `copy_u8_forward_advance`
+      core::arch::asm! {
+        "1:",
+        "subs    {count}, {count}, #2",
+        "ldrhge  {temp}, [{src}], #2",
+        "strhge  {temp}, [{dest}], #2",
+        "bgt     1b",
+        dest = inout(reg) dest,
+        src = inout(reg) src,
+        count = inout(reg) count,
+        temp = out(reg) _,
+        options(nostack)
+      }
+    }
+    no: {
+      cfg_thumb2! {
+        yes: {
+          // Same as the `armv4t` loop, but with an IT block.
+          core::arch::asm! {
+            "1:",
+            "subs    {count}, {count}, #2",
+            "itt     ge",
+            "ldrhge  {temp}, [{src}], #2",
+            "strhge  {temp}, [{dest}], #2",
+            "bgt     1b",
+            dest = inout(reg) dest,
+            src = inout(reg) src,
+            count = inout(reg) count,
+            temp = out(reg) _,
+            options(nostack)
+          }
+        }
+        no: {
+          #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+          simd128_bulk_forward!(dest, src, count);
+          // volatile for the same reason as in `copy_u8_forward_advance`
+          while count >= 2 {
+            dest.write_volatile(src.read_volatile());
+            dest = dest.add(1);
+            src = src.add(1);
+            count -= 2;
+          }
+        }
+      }
+    }
+  }
+  // The ASM loop will always underflow the `count` value, so we do a bit test
+  // to check to test for when there's a 1-byte copy at the end.
+  if (count & 1) != 0 {
+    *dest.cast::<mu_u8>() = *src.cast::<mu_u8>();
+    dest = dest.byte_add(1);
+    src = src.byte_add(1);
+  }
+  (dest, src)
+}
@@ -0,0 +1,38 @@
+/// A `const fn` version of [`copy_u16_forward`](crate::copy_u16_forward), for
+/// slices.
+///
+/// Copies `count` bytes from the start of `src` to the start of `dest`, going
+/// upward.
+///
+/// If `count` isn't a multiple of 2 then the last element is only partly
+/// copied (its lowest addressed bytes), the same as with
+/// [`copy_u16_forward`](crate::copy_u16_forward).
+///
+/// ## Panics
+/// * If `count` is more than the number of bytes in `dest` or in `src`.
+#[inline]
+pub const fn copy_u16_forward_const(
+  dest: &mut [u16], src: &[u16], count: usize,
+) {
+  assert!(
+    count.div_ceil(2) <= dest.len() && count.div_ceil(2) <= src.len(),
+    "`count` is out of bounds"
+  );
+  let whole = count / 2;
+  let mut i = 0;
+  while i < whole {
+    dest[i] = src[i];
+    i += 1;
+  }
+  let tail = count % 2;
+  if tail != 0 {
+    let mut d = dest[whole].to_ne_bytes();
+    let s = src[whole].to_ne_bytes();
+    let mut j = 0;
+    while j < tail {
+      d[j] = s[j];
+      j += 1;
+    }
+    dest[whole] = u16::from_ne_bytes(d);
+  }
+}
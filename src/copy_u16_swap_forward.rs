@@ -0,0 +1,86 @@
+use crate::*;
+
+/// Copies `count` bytes from `src` to `dest`, going upward in address value,
+/// and swaps the two bytes of each `u16` along the way (converting between
+/// little-endian and big-endian).
+///
+/// On ARMv4T, which has no `rev16` instruction, each halfword is swapped with
+/// a shift and an `orr`. With Thumb-2 it's done with `rev16`. Otherwise,
+/// including ARMv6 and later in ARM mode (which only have the `armv4t` and
+/// `thumb2` features to pick the asm), the Rust fallback uses
+/// [`u16::swap_bytes`], which LLVM turns into `rev` when the target has it.
+///
+/// ## Safety
+/// * The same as [`copy_u16_forward`], and also:
+/// * `count` must be a multiple of 2.
+/// * The `src` values must be initialized, since they're rearranged rather than
+///   just copied.
+#[inline]
+#[cfg_attr(place_copies, link_section = section_name!("copy_u16_swap_forward"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub unsafe extern "C" fn copy_u16_swap_forward(
+  mut dest: *mut mu_u16, mut src: *const u16, mut count: usize,
+) {
+  check_copy_forward(
+    "copy_u16_swap_forward",
+    dest as usize,
+    src as usize,
+    count,
+    2,
+  );
+  check_whole_elements("copy_u16_swap_forward", count, 2);
+  cfg_armv4t! {
+    yes: {
+      core::arch::asm! {
+        // The loop reasoning here is similar to `copy_u8_forward_advance`
+        "1:",
+        "subs    {count}, {count}, #2",
+        "ldrhge  {x}, [{src}], #2",
+        // x = 0x0000AABB, temp = 0x00AABB00
+        "lslge   {temp}, {x}, #8",
+        // x = 0x00AABBAA, and the store only keeps the low half, 0xBBAA
+        "orrge   {x}, {temp}, {x}, lsr #8",
+        "strhge  {x}, [{dest}], #2",
+        "bgt     1b",
+        dest = inout(reg) dest => _,
+        src = inout(reg) src => _,
+        count = inout(reg) count => _,
+        x = out(reg) _,
+        temp = out(reg) _,
+        options(nostack)
+      }
+    }
+    no: {
+      cfg_thumb2! {
+        yes: {
+          core::arch::asm! {
+            "1:",
+            "subs    {count}, {count}, #2",
+            "ittt    ge",
+            "ldrhge  {x}, [{src}], #2",
+            "rev16ge {x}, {x}",
+            "strhge  {x}, [{dest}], #2",
+            "bgt     1b",
+            dest = inout(reg) dest => _,
+            src = inout(reg) src => _,
+            count = inout(reg) count => _,
+            x = out(reg) _,
+            options(nostack)
+          }
+        }
+        no: {
+          // volatile for the same reason as in `copy_u8_forward_advance`
+          while count >= 2 {
+            dest.cast::<u16>().write_volatile(src.read_volatile().swap_bytes());
+            dest = dest.add(1);
+            src = src.add(1);
+            count -= 2;
+          }
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,125 @@
+use crate::*;
+
+/// Like [`copy_u32_backward`], but returns the `dest` and `src` pointers moved
+/// back by `count` bytes, which are the one-past-the-end pointers for the next
+/// backward copy.
+///
+/// ## Safety
+/// * The same as [`copy_u32_backward`].
+#[inline]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub unsafe fn copy_u32_backward_advance(
+  dest: *mut mu_u32, src: *const mu_u32, count: usize,
+) -> (*mut mu_u32, *const mu_u32) {
+  check_copy_backward(
+    "copy_u32_backward_advance",
+    dest as usize,
+    src as usize,
+    count,
+    4,
+  );
+  copy_u32_backward_unchecked(dest, src, count)
+}
+
+/// The body of [`copy_u32_backward_advance`] without the contract check, shared
+/// with [`copy_u32_backward`] so that the check only happens once.
+#[inline(always)]
+pub(crate) unsafe fn copy_u32_backward_unchecked(
+  mut dest: *mut mu_u32, mut src: *const mu_u32, mut count: usize,
+) -> (*mut mu_u32, *const mu_u32) {
+  // IMPORTANT: in the backward loop we adjust the pointers *before* the copy,
+  // instead of after the copy like the forward loop does.
+  cfg_armv4t! {
+    yes: {
+      // The loop reasoning here is similar to `copy_u8_backward_advance`
+      core::arch::asm! {
+        "1:",
+        "subs    {count}, {count}, #4",
+        "ldrge   {temp}, [{src}, #-4]!",
+        "strge   {temp}, [{dest}, #-4]!",
+        "bgt     1b",
+
+        // temp = count << 31;
+        // this puts bit 1 as the carry flag,
+        // and bit 0 as the neg flag
+        "lsls    {temp}, {count}, #31",
+        // if count bit 1 set, copy 2
+        "ldrhcs  {temp}, [{src}, #-2]!",
+        "strhcs  {temp}, [{dest}, #-2]!",
+        // if count bit 0 set, copy 1
+        "ldrbmi  {temp}, [{src}, #-1]!",
+        "strbmi  {temp}, [{dest}, #-1]!",
+
+        dest = inout(reg) dest,
+        src = inout(reg) src,
+        count = inout(reg) count => _,
+        temp = out(reg) _,
+        options(nostack)
+      }
+    }
+    no: {
+      cfg_thumb2! {
+        yes: {
+          // The loop reasoning here is similar to `copy_u32_forward_advance`
+          core::arch::asm! {
+            "1:",
+            "subs    {count}, {count}, #16",
+            "itt     ge",
+            "ldmdbge {src}!, {{r3, r4, r5, r12}}",
+            "stmdbge {dest}!, {{r3, r4, r5, r12}}",
+            "bgt     1b",
+
+            "lsls    r12, {count}, #29",
+            "itt     cs",
+            "ldmdbcs {src}!, {{r3, r4}}",
+            "stmdbcs {dest}!, {{r3, r4}}",
+            "itt     mi",
+            "ldrmi   r3, [{src}, #-4]!",
+            "strmi   r3, [{dest}, #-4]!",
+            "lsls    r12, {count}, #31",
+            "itt     cs",
+            "ldrhcs  r3, [{src}, #-2]!",
+            "strhcs  r3, [{dest}, #-2]!",
+            "itt     mi",
+            "ldrbmi  r3, [{src}, #-1]!",
+            "strbmi  r3, [{dest}, #-1]!",
+
+            dest = inout(reg) dest,
+            src = inout(reg) src,
+            count = inout(reg) count => _,
+            out("r3") _,
+            out("r4") _,
+            out("r5") _,
+            out("r12") _,
+            options(nostack)
+          }
+        }
+        no: {
+          #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+          simd128_bulk_backward!(dest, src, count);
+          // volatile for the same reason as in `copy_u8_forward_advance`
+          while count >= 4 {
+            dest = dest.sub(1);
+            src = src.sub(1);
+            dest.write_volatile(src.read_volatile());
+            count -= 4;
+          }
+          if (count & 0b10) != 0 {
+            dest = dest.byte_sub(2);
+            src = src.byte_sub(2);
+            *dest.cast::<mu_u16>() = *src.cast::<mu_u16>();
+          }
+          if (count & 1) != 0 {
+            dest = dest.byte_sub(1);
+            src = src.byte_sub(1);
+            *dest.cast::<mu_u8>() = *src.cast::<mu_u8>();
+          }
+        }
+      }
+    }
+  }
+  (dest, src)
+}
@@ -0,0 +1,43 @@
+/// A `const fn` version of [`copy_u32_backward`](crate::copy_u32_backward), for
+/// slices.
+///
+/// Copies the last `count` bytes of `src` to the last `count` bytes of `dest`,
+/// going downward. This is what [`copy_u32_backward`](crate::copy_u32_backward)
+/// does when given the one-past-the-end pointers of the slices.
+///
+/// If `count` isn't a multiple of 4 then the first element is only partly
+/// copied (its highest addressed bytes), the same as with
+/// [`copy_u32_backward`](crate::copy_u32_backward).
+///
+/// ## Panics
+/// * If `count` is more than the number of bytes in `dest` or in `src`.
+#[inline]
+pub const fn copy_u32_backward_const(
+  dest: &mut [u32], src: &[u32], count: usize,
+) {
+  assert!(
+    count.div_ceil(4) <= dest.len() && count.div_ceil(4) <= src.len(),
+    "`count` is out of bounds"
+  );
+  let (mut d, mut s) = (dest.len(), src.len());
+  let mut i = 0;
+  while i < count / 4 {
+    d -= 1;
+    s -= 1;
+    dest[d] = src[s];
+    i += 1;
+  }
+  let tail = count % 4;
+  if tail != 0 {
+    d -= 1;
+    s -= 1;
+    let mut d_bytes = dest[d].to_ne_bytes();
+    let s_bytes = src[s].to_ne_bytes();
+    let mut j = 4 - tail;
+    while j < 4 {
+      d_bytes[j] = s_bytes[j];
+      j += 1;
+    }
+    dest[d] = u32::from_ne_bytes(d_bytes);
+  }
+}
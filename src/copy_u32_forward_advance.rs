@@ -0,0 +1,132 @@
+use crate::*;
+
+/// Like [`copy_u32_forward`], but returns the `dest` and `src` pointers
+/// advanced by `count` bytes, which is where the next forward copy would
+/// continue from.
+///
+/// ## Safety
+/// * The same as [`copy_u32_forward`].
+#[inline]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub unsafe fn copy_u32_forward_advance(
+  dest: *mut mu_u32, src: *const mu_u32, count: usize,
+) -> (*mut mu_u32, *const mu_u32) {
+  check_copy_forward(
+    "copy_u32_forward_advance",
+    dest as usize,
+    src as usize,
+    count,
+    4,
+  );
+  copy_u32_forward_unchecked(dest, src, count)
+}
+
+/// The body of [`copy_u32_forward_advance`] without the contract check, shared
+/// with [`copy_u32_forward`] so that the check only happens once.
+#[inline(always)]
+pub(crate) unsafe fn copy_u32_forward_unchecked(
+  mut dest: *mut mu_u32, mut src: *const mu_u32, mut count: usize,
+) -> (*mut mu_u32, *const mu_u32) {
+  cfg_armv4t! {
+    yes: {
+      core::arch::asm! {
+        // The loop reasoning here is similar to `copy_u8_forward_advance`
+        "1:",
+        "subs    {count}, {count}, #4",
+        "ldrge   {temp}, [{src}], #4",
+        "strge   {temp}, [{dest}], #4",
+        "bgt     1b",
+
+        // temp = count << 31;
+        // this puts bit 1 as the carry flag,
+        // and bit 0 as the neg flag
+        "lsls    {temp}, {count}, #31",
+        // if count bit 1 set, copy 2
+        "ldrhcs  {temp}, [{src}], #2",
+        "strhcs  {temp}, [{dest}], #2",
+        // if count bit 0 set, copy 1
+        "ldrbmi  {temp}, [{src}], #1",
+        "strbmi  {temp}, [{dest}], #1",
+
+        dest = inout(reg) dest,
+        src = inout(reg) src,
+        count = inout(reg) count => _,
+        temp = out(reg) _,
+        options(nostack)
+      }
+    }
+    no: {
+      cfg_thumb2! {
+        yes: {
+          core::arch::asm! {
+            // Move 16 bytes per loop using `ldm`/`stm`. The register list
+            // must be in ascending order, so we name the registers directly.
+            "1:",
+            "subs    {count}, {count}, #16",
+            "itt     ge",
+            "ldmge   {src}!, {{r3, r4, r5, r12}}",
+            "stmge   {dest}!, {{r3, r4, r5, r12}}",
+            "bgt     1b",
+
+            // Subtracting 16 never changes the low 4 bits of `count`, so
+            // those bits still tell us which tail copies to do.
+            // r12 = count << 29;
+            // this puts bit 3 as the carry flag,
+            // and bit 2 as the neg flag
+            "lsls    r12, {count}, #29",
+            // if count bit 3 set, copy 8
+            "itt     cs",
+            "ldmcs   {src}!, {{r3, r4}}",
+            "stmcs   {dest}!, {{r3, r4}}",
+            // if count bit 2 set, copy 4
+            "itt     mi",
+            "ldrmi   r3, [{src}], #4",
+            "strmi   r3, [{dest}], #4",
+            // Then the same bit 1 and bit 0 check as the `armv4t` code.
+            "lsls    r12, {count}, #31",
+            "itt     cs",
+            "ldrhcs  r3, [{src}], #2",
+            "strhcs  r3, [{dest}], #2",
+            "itt     mi",
+            "ldrbmi  r3, [{src}], #1",
+            "strbmi  r3, [{dest}], #1",
+
+            dest = inout(reg) dest,
+            src = inout(reg) src,
+            count = inout(reg) count => _,
+            out("r3") _,
+            out("r4") _,
+            out("r5") _,
+            out("r12") _,
+            options(nostack)
+          }
+        }
+        no: {
+          #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+          simd128_bulk_forward!(dest, src, count);
+          // volatile for the same reason as in `copy_u8_forward_advance`
+          while count >= 4 {
+            dest.write_volatile(src.read_volatile());
+            dest = dest.add(1);
+            src = src.add(1);
+            count -= 4;
+          }
+          if (count & 0b10) != 0 {
+            *dest.cast::<mu_u16>() = *src.cast::<mu_u16>();
+            dest = dest.byte_add(2);
+            src = src.byte_add(2);
+          }
+          if (count & 1) != 0 {
+            *dest.cast::<mu_u8>() = *src.cast::<mu_u8>();
+            dest = dest.byte_add(1);
+            src = src.byte_add(1);
+          }
+        }
+      }
+    }
+  }
+  (dest, src)
+}
@@ -0,0 +1,38 @@
+/// A `const fn` version of [`copy_u32_forward`](crate::copy_u32_forward), for
+/// slices.
+///
+/// Copies `count` bytes from the start of `src` to the start of `dest`, going
+/// upward.
+///
+/// If `count` isn't a multiple of 4 then the last element is only partly
+/// copied (its lowest addressed bytes), the same as with
+/// [`copy_u32_forward`](crate::copy_u32_forward).
+///
+/// ## Panics
+/// * If `count` is more than the number of bytes in `dest` or in `src`.
+#[inline]
+pub const fn copy_u32_forward_const(
+  dest: &mut [u32], src: &[u32], count: usize,
+) {
+  assert!(
+    count.div_ceil(4) <= dest.len() && count.div_ceil(4) <= src.len(),
+    "`count` is out of bounds"
+  );
+  let whole = count / 4;
+  let mut i = 0;
+  while i < whole {
+    dest[i] = src[i];
+    i += 1;
+  }
+  let tail = count % 4;
+  if tail != 0 {
+    let mut d = dest[whole].to_ne_bytes();
+    let s = src[whole].to_ne_bytes();
+    let mut j = 0;
+    while j < tail {
+      d[j] = s[j];
+      j += 1;
+    }
+    dest[whole] = u32::from_ne_bytes(d);
+  }
+}
@@ -0,0 +1,91 @@
+use crate::*;
+
+/// Copies `count` bytes from `src` to `dest`, going upward in address value,
+/// and reverses the bytes of each `u32` along the way (converting between
+/// little-endian and big-endian).
+///
+/// On ARMv4T, which has no `rev` instruction, each word is reversed with the
+/// classic `eor`/`ror` sequence. With Thumb-2 it's done with `rev`.
+/// Otherwise, including ARMv6 and later in ARM mode (which only have the
+/// `armv4t` and `thumb2` features to pick the asm), the Rust fallback uses
+/// [`u32::swap_bytes`], which LLVM turns into `rev` when the target has it.
+///
+/// ## Safety
+/// * The same as [`copy_u32_forward`], and also:
+/// * `count` must be a multiple of 4.
+/// * The `src` values must be initialized, since they're rearranged rather than
+///   just copied.
+#[inline]
+#[cfg_attr(place_copies, link_section = section_name!("copy_u32_swap_forward"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub unsafe extern "C" fn copy_u32_swap_forward(
+  mut dest: *mut mu_u32, mut src: *const u32, mut count: usize,
+) {
+  check_copy_forward(
+    "copy_u32_swap_forward",
+    dest as usize,
+    src as usize,
+    count,
+    4,
+  );
+  check_whole_elements("copy_u32_swap_forward", count, 4);
+  cfg_armv4t! {
+    yes: {
+      core::arch::asm! {
+        // The loop reasoning here is similar to `copy_u8_forward_advance`
+        "1:",
+        "subs    {count}, {count}, #4",
+        "ldrge   {x}, [{src}], #4",
+        // x = 0xAABBCCDD
+        // temp = x ^ x.rotate_right(16) = 0xAA^CC, BB^DD, CC^AA, DD^BB
+        "eorge   {temp}, {x}, {x}, ror #16",
+        // temp = 0xAA^CC, 00, CC^AA, DD^BB
+        "bicge   {temp}, {temp}, #0xFF0000",
+        // x = x.rotate_right(8) = 0xDDAABBCC
+        "movge   {x}, {x}, ror #8",
+        // x ^= temp >> 8, which is 0x00, AA^CC, 00, CC^AA, so x = 0xDDCCBBAA
+        "eorge   {x}, {x}, {temp}, lsr #8",
+        "strge   {x}, [{dest}], #4",
+        "bgt     1b",
+        dest = inout(reg) dest => _,
+        src = inout(reg) src => _,
+        count = inout(reg) count => _,
+        x = out(reg) _,
+        temp = out(reg) _,
+        options(nostack)
+      }
+    }
+    no: {
+      cfg_thumb2! {
+        yes: {
+          core::arch::asm! {
+            "1:",
+            "subs    {count}, {count}, #4",
+            "ittt    ge",
+            "ldrge   {x}, [{src}], #4",
+            "revge   {x}, {x}",
+            "strge   {x}, [{dest}], #4",
+            "bgt     1b",
+            dest = inout(reg) dest => _,
+            src = inout(reg) src => _,
+            count = inout(reg) count => _,
+            x = out(reg) _,
+            options(nostack)
+          }
+        }
+        no: {
+          // volatile for the same reason as in `copy_u8_forward_advance`
+          while count >= 4 {
+            dest.cast::<u32>().write_volatile(src.read_volatile().swap_bytes());
+            dest = dest.add(1);
+            src = src.add(1);
+            count -= 4;
+          }
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,127 @@
+use crate::*;
+
+/// Copies `count` bytes from `src` to `dest`, starting at one-past-the-end.
+///
+/// Copies are done in 8-byte chunks as much as possible. If the number of bytes
+/// to copy is not a multiple of 8 then the last portion will be done using a
+/// 4-byte, 2-byte, and/or 1-byte copy.
+///
+/// ## Safety
+/// * If `count` is zero, the `src` and `dest` pointers are not accessed, and
+///   they can even be invalid or null.
+/// * If `count` is non-zero, then both `src` and `dest` must be aligned to 8,
+///   be the one-past-the-end pointers for `count` bytes backward, and one of
+///   the following must be true:
+///   * The `src` and `dest` regions are entirely disjoint.
+///   * `src` equals `dest` (there is exact overlap).
+///   * `src` is *less* than `dest` (a partial overlap).
+/// * `count` may not exceed `isize::MAX as usize`. (All Rust allocations
+///   already follow this rule, but perhaps it's worth stating that it is an
+///   assumption of the function.)
+#[inline]
+#[cfg_attr(place_copies, link_section = section_name!("copy_u64_backward"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub unsafe extern "C" fn copy_u64_backward(
+  mut dest: *mut mu_u64, mut src: *const mu_u64, mut count: usize,
+) {
+  check_copy_backward(
+    "copy_u64_backward",
+    dest as usize,
+    src as usize,
+    count,
+    8,
+  );
+  // IMPORTANT: in the backward loop we adjust the pointers *before* the copy,
+  // instead of after the copy like the forward loop does.
+  cfg_armv4t! {
+    yes: {
+      // The loop reasoning here is similar to `copy_u64_forward`
+      core::arch::asm! {
+        "1:",
+        "subs    {count}, {count}, #8",
+        "ldmdbge {src}!, {{r3, r12}}",
+        "stmdbge {dest}!, {{r3, r12}}",
+        "bgt     1b",
+
+        "lsls    r12, {count}, #30",
+        "ldrcs   r3, [{src}, #-4]!",
+        "strcs   r3, [{dest}, #-4]!",
+        "ldrhmi  r3, [{src}, #-2]!",
+        "strhmi  r3, [{dest}, #-2]!",
+        "lsls    r12, {count}, #31",
+        "ldrbmi  r3, [{src}, #-1]!",
+        "strbmi  r3, [{dest}, #-1]!",
+
+        dest = inout(reg) dest => _,
+        src = inout(reg) src => _,
+        count = inout(reg) count => _,
+        out("r3") _,
+        out("r12") _,
+        options(nostack)
+      }
+    }
+    no: {
+      cfg_thumb2! {
+        yes: {
+          // The loop reasoning here is similar to `copy_u64_forward`
+          core::arch::asm! {
+            "1:",
+            "subs    {count}, {count}, #8",
+            "itt     ge",
+            "ldrdge  r2, r3, [{src}, #-8]!",
+            "strdge  r2, r3, [{dest}, #-8]!",
+            "bgt     1b",
+
+            "lsls    r3, {count}, #30",
+            "itt     cs",
+            "ldrcs   r2, [{src}, #-4]!",
+            "strcs   r2, [{dest}, #-4]!",
+            "itt     mi",
+            "ldrhmi  r2, [{src}, #-2]!",
+            "strhmi  r2, [{dest}, #-2]!",
+            "lsls    r3, {count}, #31",
+            "itt     mi",
+            "ldrbmi  r2, [{src}, #-1]!",
+            "strbmi  r2, [{dest}, #-1]!",
+
+            dest = inout(reg) dest => _,
+            src = inout(reg) src => _,
+            count = inout(reg) count => _,
+            out("r2") _,
+            out("r3") _,
+            options(nostack)
+          }
+        }
+        no: {
+          #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+          simd128_bulk_backward!(dest, src, count);
+          // volatile for the same reason as in `copy_u8_forward_advance`
+          while count >= 8 {
+            dest = dest.sub(1);
+            src = src.sub(1);
+            dest.write_volatile(src.read_volatile());
+            count -= 8;
+          }
+          if (count & 0b100) != 0 {
+            dest = dest.byte_sub(4);
+            src = src.byte_sub(4);
+            *dest.cast::<mu_u32>() = *src.cast::<mu_u32>();
+          }
+          if (count & 0b10) != 0 {
+            dest = dest.byte_sub(2);
+            src = src.byte_sub(2);
+            *dest.cast::<mu_u16>() = *src.cast::<mu_u16>();
+          }
+          if (count & 1) != 0 {
+            dest = dest.byte_sub(1);
+            src = src.byte_sub(1);
+            *dest.cast::<mu_u8>() = *src.cast::<mu_u8>();
+          }
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,131 @@
+use crate::*;
+
+/// Copies `count` bytes from `src` to `dest`, going upward in address value.
+///
+/// Copies are done in 8-byte chunks as much as possible. If the number of bytes
+/// to copy is not a multiple of 8 then the last portion will be done using a
+/// 4-byte, 2-byte, and/or 1-byte copy.
+///
+/// ## Safety
+/// * If `count` is zero, the `src` and `dest` pointers are not accessed, and
+///   they can even be invalid or null.
+/// * If `count` is non-zero, then both `src` and `dest` must be aligned to 8,
+///   valid for `count` bytes forward, and one of the following must be true:
+///   * The `src` and `dest` regions are entirely disjoint.
+///   * `src` equals `dest` (there is exact overlap).
+///   * `src` is *greater* than `dest` (a partial overlap).
+/// * `count` may not exceed `isize::MAX as usize`. (All Rust allocations
+///   already follow this rule, but perhaps it's worth stating that it is an
+///   assumption of the function.)
+#[inline]
+#[cfg_attr(place_copies, link_section = section_name!("copy_u64_forward"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub unsafe extern "C" fn copy_u64_forward(
+  mut dest: *mut mu_u64, mut src: *const mu_u64, mut count: usize,
+) {
+  check_copy_forward("copy_u64_forward", dest as usize, src as usize, count, 8);
+  cfg_armv4t! {
+    yes: {
+      // ARMv4T doesn't have `ldrd`/`strd`, so we move a pair of registers with
+      // `ldm`/`stm`. The register list must be in ascending order, so we name
+      // the registers directly.
+      core::arch::asm! {
+        // The loop reasoning here is similar to `copy_u8_forward_advance`
+        "1:",
+        "subs    {count}, {count}, #8",
+        "ldmge   {src}!, {{r3, r12}}",
+        "stmge   {dest}!, {{r3, r12}}",
+        "bgt     1b",
+
+        // Subtracting 8 never changes the low 3 bits of `count`, so those bits
+        // still tell us which tail copies to do.
+        // r12 = count << 30;
+        // this puts bit 2 as the carry flag,
+        // and bit 1 as the neg flag
+        "lsls    r12, {count}, #30",
+        // if count bit 2 set, copy 4
+        "ldrcs   r3, [{src}], #4",
+        "strcs   r3, [{dest}], #4",
+        // if count bit 1 set, copy 2
+        "ldrhmi  r3, [{src}], #2",
+        "strhmi  r3, [{dest}], #2",
+        // r12 = count << 31;
+        // this puts bit 0 as the neg flag
+        "lsls    r12, {count}, #31",
+        // if count bit 0 set, copy 1
+        "ldrbmi  r3, [{src}], #1",
+        "strbmi  r3, [{dest}], #1",
+
+        dest = inout(reg) dest => _,
+        src = inout(reg) src => _,
+        count = inout(reg) count => _,
+        out("r3") _,
+        out("r12") _,
+        options(nostack)
+      }
+    }
+    no: {
+      cfg_thumb2! {
+        yes: {
+          // `ldrd`/`strd` need an even/odd pair of registers in A32 (Thumb-2
+          // allows any pair), so we name a pair that works for both.
+          core::arch::asm! {
+            "1:",
+            "subs    {count}, {count}, #8",
+            "itt     ge",
+            "ldrdge  r2, r3, [{src}], #8",
+            "strdge  r2, r3, [{dest}], #8",
+            "bgt     1b",
+
+            // Then the same tail as the `armv4t` code.
+            "lsls    r3, {count}, #30",
+            "itt     cs",
+            "ldrcs   r2, [{src}], #4",
+            "strcs   r2, [{dest}], #4",
+            "itt     mi",
+            "ldrhmi  r2, [{src}], #2",
+            "strhmi  r2, [{dest}], #2",
+            "lsls    r3, {count}, #31",
+            "itt     mi",
+            "ldrbmi  r2, [{src}], #1",
+            "strbmi  r2, [{dest}], #1",
+
+            dest = inout(reg) dest => _,
+            src = inout(reg) src => _,
+            count = inout(reg) count => _,
+            out("r2") _,
+            out("r3") _,
+            options(nostack)
+          }
+        }
+        no: {
+          #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+          simd128_bulk_forward!(dest, src, count);
+          // volatile for the same reason as in `copy_u8_forward_advance`
+          while count >= 8 {
+            dest.write_volatile(src.read_volatile());
+            dest = dest.add(1);
+            src = src.add(1);
+            count -= 8;
+          }
+          if (count & 0b100) != 0 {
+            *dest.cast::<mu_u32>() = *src.cast::<mu_u32>();
+            dest = dest.byte_add(4);
+            src = src.byte_add(4);
+          }
+          if (count & 0b10) != 0 {
+            *dest.cast::<mu_u16>() = *src.cast::<mu_u16>();
+            dest = dest.byte_add(2);
+            src = src.byte_add(2);
+          }
+          if (count & 1) != 0 {
+            *dest.cast::<mu_u8>() = *src.cast::<mu_u8>();
+          }
+        }
+      }
+    }
+  }
+}
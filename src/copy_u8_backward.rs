@@ -15,39 +15,20 @@ use crate::*;
 ///   already follow this rule, but perhaps it's worth stating that it is an
 ///   assumption of the function.)
 #[inline]
-#[cfg_attr(feature = "link_iwram", link_section = ".iwram.copy_u8_backward")]
+#[cfg_attr(place_copies, link_section = section_name!("copy_u8_backward"))]
 #[cfg_attr(
   all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
   instruction_set(arm::a32)
 )]
 pub unsafe extern "C" fn copy_u8_backward(
-  mut dest: *mut mu_u8, mut src: *const mu_u8, mut count: usize,
+  dest: *mut mu_u8, src: *const mu_u8, count: usize,
 ) {
-  // IMPORTANT: in the backward loop we adjust the pointers *before* the copy,
-  // instead of after the copy like the forward loop does.
-  cfg_armv4t! {
-    yes: {
-      // The loop reasoning here is similar to `copy_u8_forward`
-      core::arch::asm! {
-        "1:",
-        "subs    {count}, {count}, #1",
-        "ldrbge  {temp}, [{src}, #-1]!",
-        "strbge  {temp}, [{dest}, #-1]!",
-        "bgt     1b",
-        dest = inout(reg) dest => _,
-        src = inout(reg) src => _,
-        count = inout(reg) count => _,
-        temp = out(reg) _,
-        options(nostack)
-      }
-    }
-    no: {
-      while count >= 1 {
-        dest = dest.sub(1);
-        src = src.sub(1);
-        *dest = *src;
-        count -= 1;
-      }
-    }
-  }
+  check_copy_backward(
+    "copy_u8_backward",
+    dest as usize,
+    src as usize,
+    count,
+    1,
+  );
+  copy_u8_backward_unchecked(dest, src, count);
 }
@@ -0,0 +1,84 @@
+use crate::*;
+
+/// Like [`copy_u8_backward`], but returns the `dest` and `src` pointers moved
+/// back by `count` bytes, which are the one-past-the-end pointers for the next
+/// backward copy.
+///
+/// ## Safety
+/// * The same as [`copy_u8_backward`].
+#[inline]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub unsafe fn copy_u8_backward_advance(
+  dest: *mut mu_u8, src: *const mu_u8, count: usize,
+) -> (*mut mu_u8, *const mu_u8) {
+  check_copy_backward(
+    "copy_u8_backward_advance",
+    dest as usize,
+    src as usize,
+    count,
+    1,
+  );
+  copy_u8_backward_unchecked(dest, src, count)
+}
+
+/// The body of [`copy_u8_backward_advance`] without the contract check, shared
+/// with [`copy_u8_backward`] so that the check only happens once.
+#[inline(always)]
+pub(crate) unsafe fn copy_u8_backward_unchecked(
+  mut dest: *mut mu_u8, mut src: *const mu_u8, mut count: usize,
+) -> (*mut mu_u8, *const mu_u8) {
+  // IMPORTANT: in the backward loop we adjust the pointers *before* the copy,
+  // instead of after the copy like the forward loop does.
+  cfg_armv4t! {
+    yes: {
+      // The loop reasoning here is similar to `copy_u8_forward_advance`
+      core::arch::asm! {
+        "1:",
+        "subs    {count}, {count}, #1",
+        "ldrbge  {temp}, [{src}, #-1]!",
+        "strbge  {temp}, [{dest}, #-1]!",
+        "bgt     1b",
+        dest = inout(reg) dest,
+        src = inout(reg) src,
+        count = inout(reg) count => _,
+        temp = out(reg) _,
+        options(nostack)
+      }
+    }
+    no: {
+      cfg_thumb2! {
+        yes: {
+          // Same as the `armv4t` loop, but with an IT block.
+          core::arch::asm! {
+            "1:",
+            "subs    {count}, {count}, #1",
+            "itt     ge",
+            "ldrbge  {temp}, [{src}, #-1]!",
+            "strbge  {temp}, [{dest}, #-1]!",
+            "bgt     1b",
+            dest = inout(reg) dest,
+            src = inout(reg) src,
+            count = inout(reg) count => _,
+            temp = out(reg) _,
+            options(nostack)
+          }
+        }
+        no: {
+          #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+          simd128_bulk_backward!(dest, src, count);
+          // volatile for the same reason as in `copy_u8_forward_advance`
+          while count >= 1 {
+            dest = dest.sub(1);
+            src = src.sub(1);
+            dest.write_volatile(src.read_volatile());
+            count -= 1;
+          }
+        }
+      }
+    }
+  }
+  (dest, src)
+}
@@ -0,0 +1,24 @@
+/// A `const fn` version of [`copy_u8_backward`](crate::copy_u8_backward), for
+/// slices.
+///
+/// Copies the last `count` bytes of `src` to the last `count` bytes of `dest`,
+/// going downward. This is what [`copy_u8_backward`](crate::copy_u8_backward)
+/// does when given the one-past-the-end pointers of the slices.
+///
+/// ## Panics
+/// * If `count` is more than the number of bytes in `dest` or in `src`.
+#[inline]
+pub const fn copy_u8_backward_const(dest: &mut [u8], src: &[u8], count: usize) {
+  assert!(
+    count <= dest.len() && count <= src.len(),
+    "`count` is out of bounds"
+  );
+  let (mut d, mut s) = (dest.len(), src.len());
+  let mut i = 0;
+  while i < count {
+    d -= 1;
+    s -= 1;
+    dest[d] = src[s];
+    i += 1;
+  }
+}
@@ -0,0 +1,20 @@
+/// A `const fn` version of [`copy_u8_forward`](crate::copy_u8_forward), for
+/// slices.
+///
+/// Copies `count` bytes from the start of `src` to the start of `dest`, going
+/// upward.
+///
+/// ## Panics
+/// * If `count` is more than the number of bytes in `dest` or in `src`.
+#[inline]
+pub const fn copy_u8_forward_const(dest: &mut [u8], src: &[u8], count: usize) {
+  assert!(
+    count <= dest.len() && count <= src.len(),
+    "`count` is out of bounds"
+  );
+  let mut i = 0;
+  while i < count {
+    dest[i] = src[i];
+    i += 1;
+  }
+}
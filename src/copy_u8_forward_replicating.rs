@@ -0,0 +1,81 @@
+use crate::*;
+
+/// Copies `count` bytes from `src` to `dest` one at a time, going upward in
+/// address value, and reading each byte only after the one before it has been
+/// written.
+///
+/// Unlike [`copy_u8_forward`], this allows `src` to be *less* than `dest` with
+/// the regions overlapping. Then the bytes from `src` up to `dest` repeat
+/// through the rest of `dest`, every `dest - src` bytes, which is how an LZ77
+/// back-reference works.
+///
+/// ## Safety
+/// * If `count` is zero, the `src` and `dest` pointers are not accessed, and
+///   they can even be invalid or null.
+/// * If `count` is non-zero, then both `src` and `dest` must be valid for
+///   `count` bytes forward. The regions can overlap in any way.
+/// * `count` may not exceed `isize::MAX as usize`. (All Rust allocations
+///   already follow this rule, but perhaps it's worth stating that it is an
+///   assumption of the function.)
+#[inline]
+#[cfg_attr(
+  place_copies,
+  link_section = section_name!("copy_u8_forward_replicating")
+)]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub unsafe extern "C" fn copy_u8_forward_replicating(
+  mut dest: *mut mu_u8, mut src: *const mu_u8, mut count: usize,
+) {
+  cfg_armv4t! {
+    yes: {
+      // The same loop as `copy_u8_forward_advance`, which already loads each
+      // byte after storing the one before it.
+      core::arch::asm! {
+        "1:",
+        "subs    {count}, {count}, #1",
+        "ldrbge  {temp}, [{src}], #1",
+        "strbge  {temp}, [{dest}], #1",
+        "bgt     1b",
+        dest = inout(reg) dest => _,
+        src = inout(reg) src => _,
+        count = inout(reg) count => _,
+        temp = out(reg) _,
+        options(nostack)
+      }
+    }
+    no: {
+      cfg_thumb2! {
+        yes: {
+          // Same as the `armv4t` loop, but with an IT block.
+          core::arch::asm! {
+            "1:",
+            "subs    {count}, {count}, #1",
+            "itt     ge",
+            "ldrbge  {temp}, [{src}], #1",
+            "strbge  {temp}, [{dest}], #1",
+            "bgt     1b",
+            dest = inout(reg) dest => _,
+            src = inout(reg) src => _,
+            count = inout(reg) count => _,
+            temp = out(reg) _,
+            options(nostack)
+          }
+        }
+        no: {
+          // There's no `simd128_bulk_forward!` here, since it would load a
+          // whole chunk before storing any of it. Volatile for the same reason
+          // as in `copy_u8_forward_advance`.
+          while count >= 1 {
+            dest.write_volatile(src.read_volatile());
+            dest = dest.add(1);
+            src = src.add(1);
+            count -= 1;
+          }
+        }
+      }
+    }
+  }
+}
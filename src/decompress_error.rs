@@ -0,0 +1,42 @@
+/// Why a decompression function couldn't decompress its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecompressError {
+  /// The header's type isn't the one for the format being decompressed.
+  WrongFormat {
+    /// The header's first byte.
+    found: u8,
+  },
+  /// The input ended before the whole output was decompressed.
+  Truncated,
+  /// The output buffer is smaller than the size in the header.
+  OutputTooSmall {
+    /// The size the output buffer needs, in units of its elements.
+    needed: usize,
+  },
+  /// A back-reference points to before the start of the output.
+  BadReference,
+  /// A Huffman tree node points past the end of the tree.
+  BadTree,
+}
+
+impl core::fmt::Display for DecompressError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      DecompressError::WrongFormat { found } => {
+        write!(f, "the header type {found:#04x} is for a different format")
+      }
+      DecompressError::Truncated => f.write_str("the input ended early"),
+      DecompressError::OutputTooSmall { needed } => {
+        write!(f, "the output needs room for {needed} elements")
+      }
+      DecompressError::BadReference => {
+        f.write_str("a back-reference points before the start of the output")
+      }
+      DecompressError::BadTree => {
+        f.write_str("a Huffman tree node points past the end of the tree")
+      }
+    }
+  }
+}
+
+impl core::error::Error for DecompressError {}
@@ -0,0 +1,161 @@
+use crate::*;
+
+/// Fills `count` bytes at `dest` with `pattern` repeated over and over, going
+/// upward in address value (like `memset_pattern16`, for any length of
+/// pattern).
+///
+/// The last repeat of the pattern is cut short if `count` isn't a multiple of
+/// the pattern's length.
+///
+/// * A 1, 2, or 4 byte pattern is stored a word at a time, after a few byte
+///   stores to align `dest`.
+/// * A pattern whose length is a multiple of 4 is written once, and then the
+///   rest is copied a word at a time from `pattern.len()` bytes behind.
+/// * Any other pattern is stored a byte at a time.
+///
+/// In all cases the last few bytes are done using a 2-byte and/or 1-byte
+/// store.
+///
+/// ## Panics
+/// * If `pattern` is empty and `count` is non-zero.
+///
+/// ## Safety
+/// * If `count` is zero, the `dest` pointer is not accessed, and it can even be
+///   invalid or null.
+/// * If `count` is non-zero, then `dest` must be valid for `count` bytes
+///   forward, and must not overlap `pattern`.
+/// * `count` may not exceed `isize::MAX as usize`.
+#[inline]
+#[cfg_attr(place_fills, link_section = section_name!("fill_pattern"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub unsafe fn fill_pattern(
+  mut dest: *mut mu_u8, pattern: &[u8], mut count: usize,
+) {
+  if count == 0 {
+    return;
+  }
+  assert!(!pattern.is_empty(), "fill_pattern: `pattern` must not be empty");
+  let len = pattern.len();
+  let word_fill = matches!(len, 1 | 2 | 4);
+  let copy_fill = !word_fill && len.is_multiple_of(4);
+  // Bytes are stored one at a time until `dest` is aligned (and, for a copy
+  // fill, one whole pattern has been written), or for the whole fill if the
+  // pattern doesn't fit words at all.
+  let align_head = (dest as usize).wrapping_neg() & 0b11;
+  let head = if word_fill {
+    align_head
+  } else if copy_fill {
+    align_head + len
+  } else {
+    count
+  };
+  let head = head.min(count);
+  let phase;
+  (dest, phase) = fill_bytes(dest, pattern, head);
+  count -= head;
+  if word_fill {
+    // Every aligned word gets the same 4 bytes, so we can keep storing one
+    // register. `len` is a power of two, so masking avoids a division (which
+    // ARMv4T would need a function call for).
+    let bytes: [u8; 4] =
+      core::array::from_fn(|i| pattern[(phase + i) & (len - 1)]);
+    let word = u32::from_ne_bytes(bytes);
+    cfg_armv4t! {
+      yes: {
+        // The loop reasoning here is similar to `copy_u8_forward_advance`
+        core::arch::asm! {
+          "1:",
+          "subs    {count}, {count}, #4",
+          "strge   {word}, [{dest}], #4",
+          "bgt     1b",
+          dest = inout(reg) dest,
+          count = inout(reg) count,
+          word = in(reg) word,
+          options(nostack)
+        }
+      }
+      no: {
+        cfg_thumb2! {
+          yes: {
+            // Same as the `armv4t` loop, but with an IT block.
+            core::arch::asm! {
+              "1:",
+              "subs    {count}, {count}, #4",
+              "it      ge",
+              "strge   {word}, [{dest}], #4",
+              "bgt     1b",
+              dest = inout(reg) dest,
+              count = inout(reg) count,
+              word = in(reg) word,
+              options(nostack)
+            }
+          }
+          no: {
+            // volatile for the same reason as in `copy_u8_forward_advance`,
+            // since LLVM can also recognize this loop as a `memset` call.
+            while count >= 4 {
+              dest.cast::<u32>().write_volatile(word);
+              dest = dest.add(4);
+              count -= 4;
+            }
+          }
+        }
+      }
+    }
+    // The ASM loops will always underflow the `count` value, but either way
+    // the low two bits are still the number of bytes left.
+    if (count & 0b10) != 0 {
+      dest.cast::<[u8; 2]>().write([bytes[0], bytes[1]]);
+      dest = dest.add(2);
+      if (count & 1) != 0 {
+        dest.cast::<u8>().write(bytes[2]);
+      }
+    } else if (count & 1) != 0 {
+      dest.cast::<u8>().write(bytes[0]);
+    }
+  } else if copy_fill && count > 0 {
+    // Each word is the same as the word `len` bytes before it, so the rest is
+    // a forward copy from `len` bytes behind. Since `len` is at least 8, each
+    // word that's read has already been written. (With `count` left over, the
+    // whole head was written, so `src` is within the fill.)
+    let mut dest = dest.cast::<mu_u32>();
+    let mut src = dest.byte_sub(len).cast_const();
+    // volatile for the same reason as in `copy_u8_forward_advance`
+    while count >= 4 {
+      dest.write_volatile(src.read_volatile());
+      dest = dest.add(1);
+      src = src.add(1);
+      count -= 4;
+    }
+    if (count & 0b10) != 0 {
+      *dest.cast::<mu_u16>() = *src.cast::<mu_u16>();
+      dest = dest.byte_add(2);
+      src = src.byte_add(2);
+    }
+    if (count & 1) != 0 {
+      *dest.cast::<mu_u8>() = *src.cast::<mu_u8>();
+    }
+  }
+}
+
+/// Writes the first `count` bytes of `pattern` repeated to `dest`, and gives
+/// the advanced `dest` and the index in `pattern` of the next byte.
+#[inline(always)]
+unsafe fn fill_bytes(
+  mut dest: *mut mu_u8, pattern: &[u8], count: usize,
+) -> (*mut mu_u8, usize) {
+  let mut phase = 0;
+  // volatile for the same reason as in `copy_u8_forward_advance`
+  for _ in 0..count {
+    dest.cast::<u8>().write_volatile(*pattern.get_unchecked(phase));
+    dest = dest.add(1);
+    phase += 1;
+    if phase == pattern.len() {
+      phase = 0;
+    }
+  }
+  (dest, phase)
+}
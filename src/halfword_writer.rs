@@ -0,0 +1,81 @@
+use crate::*;
+
+/// Puts bytes together into `u16` values, for the decompression functions that
+/// can only write whole halfwords (so that they work with VRAM).
+pub(crate) struct HalfwordWriter<'a> {
+  dest: &'a mut [u16],
+  /// The number of bytes pushed so far.
+  pos: usize,
+  /// When `pos` is odd, the byte that's waiting for its pair.
+  low: u8,
+}
+impl<'a> HalfwordWriter<'a> {
+  #[inline(always)]
+  pub(crate) fn new(dest: &'a mut [u16]) -> Self {
+    Self { dest, pos: 0, low: 0 }
+  }
+
+  /// The number of bytes pushed so far.
+  #[inline(always)]
+  pub(crate) fn pos(&self) -> usize {
+    self.pos
+  }
+
+  #[inline(always)]
+  pub(crate) fn push(&mut self, byte: u8) {
+    if self.pos.is_multiple_of(2) {
+      self.low = byte;
+    } else {
+      self.dest[self.pos / 2] = u16::from_ne_bytes([self.low, byte]);
+    }
+    self.pos += 1;
+  }
+
+  /// If the next byte would go at an address that's aligned to 4. Since
+  /// `dest` is aligned to 2, no byte is waiting for its pair when this is true.
+  #[inline(always)]
+  pub(crate) fn is_word_aligned(&self) -> bool {
+    (self.dest.as_ptr() as usize + self.pos).is_multiple_of(4)
+  }
+
+  /// Pushes `len` copies of `byte` a word at a time with [`fill_pattern`].
+  ///
+  /// ## Panics
+  /// * If the output isn't [word aligned](Self::is_word_aligned), if `len`
+  ///   isn't a multiple of 4, or if there's no room for `len` more bytes.
+  #[inline(always)]
+  pub(crate) fn fill_words(&mut self, byte: u8, len: usize) {
+    assert!(self.is_word_aligned() && len.is_multiple_of(4));
+    let pos = self.pos;
+    let words = &mut self.dest[pos / 2..(pos + len) / 2];
+    // Safety: `words` is `len` bytes, and aligned to 4 since the output is.
+    // With an aligned `dest` and a multiple of 4 bytes, `fill_pattern` only
+    // does word stores.
+    unsafe {
+      fill_pattern(words.as_mut_ptr().cast::<mu_u8>(), &[byte; 4], len);
+    }
+    self.pos += len;
+  }
+
+  /// Gives a byte that's already been pushed.
+  #[inline(always)]
+  pub(crate) fn get(&self, i: usize) -> u8 {
+    if i == self.pos - 1 && !self.pos.is_multiple_of(2) {
+      // it's still waiting in `low`
+      self.low
+    } else {
+      self.dest[i / 2].to_ne_bytes()[i % 2]
+    }
+  }
+
+  /// Writes a byte that's still waiting for its pair, combined with the high
+  /// byte already in `dest`.
+  #[inline(always)]
+  pub(crate) fn finish(self) {
+    if !self.pos.is_multiple_of(2) {
+      let last = &mut self.dest[self.pos / 2];
+      let high = last.to_ne_bytes()[1];
+      *last = u16::from_ne_bytes([self.low, high]);
+    }
+  }
+}
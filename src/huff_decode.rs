@@ -0,0 +1,68 @@
+use crate::*;
+
+/// Decodes GBA BIOS Huffman data (the `0x20` format) whose decompressed size
+/// is `size` bytes, and gives each 32-bit word of output to `emit`, along with
+/// how many of its bytes are part of the output (4, except for the last word).
+///
+/// The low 4 bits of the header are the size of each symbol in bits (4 or 8),
+/// and symbols are packed into each word starting from the low bits. Then comes
+/// the tree, with the byte after the header giving its length, and then the
+/// encoded bits, in little-endian 32-bit words read from the highest bit down.
+#[inline(always)]
+pub(crate) fn huff_decode(
+  src: &[u8], size: usize, mut emit: impl FnMut(u32, usize),
+) -> Result<(), DecompressError> {
+  let bits = src[0] & 0xF;
+  if bits != 4 && bits != 8 {
+    return Err(DecompressError::WrongFormat { found: src[0] });
+  }
+  let Some(&tree_size) = src.get(4) else {
+    return Err(DecompressError::Truncated);
+  };
+  // The tree includes its size byte, and the root node is right after that.
+  let tree_len = (usize::from(tree_size) + 1) * 2;
+  let Some(tree) = src.get(4..4 + tree_len) else {
+    return Err(DecompressError::Truncated);
+  };
+  let mut input = &src[4 + tree_len..];
+  let (mut in_word, mut in_bits) = (0_u32, 0);
+  let (mut out_word, mut out_bits) = (0_u32, 0);
+  // This avoids a division, which ARMv4T would need a function call for.
+  let symbols = if bits == 4 { size * 2 } else { size };
+  for _ in 0..symbols {
+    let mut node = 1;
+    let value = loop {
+      if in_bits == 0 {
+        let Some((word, rest)) = input.split_first_chunk::<4>() else {
+          return Err(DecompressError::Truncated);
+        };
+        (in_word, in_bits) = (u32::from_le_bytes(*word), 32);
+        input = rest;
+      }
+      let bit = (in_word >> 31) as usize;
+      in_word <<= 1;
+      in_bits -= 1;
+      // Each node has the offset to its pair of children in the low 6 bits,
+      // and bits 7 and 6 say if the first or second child is a leaf.
+      let n = tree[node];
+      let child = (node & !1) + usize::from(n & 0x3F) * 2 + 2 + bit;
+      let Some(&value) = tree.get(child) else {
+        return Err(DecompressError::BadTree);
+      };
+      if n & (0x80 >> bit) != 0 {
+        break value;
+      }
+      node = child;
+    };
+    out_word |= (u32::from(value) & ((1 << bits) - 1)) << out_bits;
+    out_bits += bits;
+    if out_bits == 32 {
+      emit(out_word, 4);
+      (out_word, out_bits) = (0, 0);
+    }
+  }
+  if out_bits > 0 {
+    emit(out_word, usize::from(out_bits / 8));
+  }
+  Ok(())
+}
@@ -0,0 +1,62 @@
+use crate::*;
+
+/// Decompresses GBA BIOS Huffman data (the `0x20` format of `HuffUnComp`) from
+/// `src` into `dest`, and gives the decompressed size in bytes.
+///
+/// The data starts with a 4-byte header, which is `0x24` or `0x28` in the low
+/// byte (for 4-bit or 8-bit symbols) and the decompressed size in the upper 24
+/// bits (little-endian). Next is the tree, starting with a byte that's half the
+/// tree's length minus 1, and then each node, where:
+/// * Bits 0 to 5 are the offset to the node's pair of children, which are at
+///   `(node_index & !1) + offset * 2 + 2` (counting the length byte as 0).
+/// * Bit 7 is set if the first child is a leaf, and bit 6 if the second child
+///   is.
+///
+/// After that are the encoded bits, in little-endian `u32` words read from the
+/// highest bit down. Starting from the root, each 0 bit goes to the first child
+/// and each 1 bit goes to the second, and a leaf's value is the next symbol.
+/// Symbols are packed into each output word starting from the low bits, and
+/// the output is stored a word at a time.
+///
+/// Only the first `size` bytes of `dest` are written.
+///
+/// This writes single bytes at the end, so it can't be used with the GBA's
+/// VRAM. For that, see [`huff_decompress_u16`].
+///
+/// ## Failure
+/// * [`DecompressError::WrongFormat`] if the header's first byte isn't `0x24`
+///   or `0x28` (the BIOS allows other symbol sizes, but they're not used).
+/// * [`DecompressError::OutputTooSmall`] if `dest` is shorter than the size.
+/// * [`DecompressError::Truncated`] if `src` ends before the output is done.
+/// * [`DecompressError::BadTree`] if a node's children are past the end of the
+///   tree.
+///
+/// When there's an error partway through, `dest` is left with the part that
+/// was already decompressed.
+#[inline]
+#[cfg_attr(place_decoders, link_section = section_name!("huff_decompress"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub fn huff_decompress(
+  src: &[u8], dest: &mut [u8],
+) -> Result<usize, DecompressError> {
+  let size = read_bios_header(src, 0x20)?;
+  let Some(dest) = dest.get_mut(..size) else {
+    return Err(DecompressError::OutputTooSmall { needed: size });
+  };
+  let mut pos = 0;
+  huff_decode(src, size, |word, n| {
+    let bytes = word.to_le_bytes();
+    if n == 4 {
+      dest[pos..pos + 4].copy_from_slice(&bytes);
+    } else {
+      for (i, &byte) in bytes[..n].iter().enumerate() {
+        dest[pos + i] = byte;
+      }
+    }
+    pos += n;
+  })?;
+  Ok(size)
+}
@@ -0,0 +1,34 @@
+use crate::*;
+
+/// Like [`huff_decompress`], but only ever writes whole `u16` values to `dest`,
+/// since the GBA's VRAM ignores or mangles single byte writes.
+///
+/// `dest` gets the same bytes in memory as [`huff_decompress`] would give. If
+/// the size is odd then the last byte is combined with the high byte already
+/// in `dest`. The size returned is still in bytes.
+///
+/// ## Failure
+/// * The same as [`huff_decompress`], except `dest` needs `size.div_ceil(2)`
+///   elements.
+#[inline]
+#[cfg_attr(place_decoders, link_section = section_name!("huff_decompress_u16"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub fn huff_decompress_u16(
+  src: &[u8], dest: &mut [u16],
+) -> Result<usize, DecompressError> {
+  let size = read_bios_header(src, 0x20)?;
+  let Some(dest) = dest.get_mut(..size.div_ceil(2)) else {
+    return Err(DecompressError::OutputTooSmall { needed: size.div_ceil(2) });
+  };
+  let mut out = HalfwordWriter::new(dest);
+  huff_decode(src, size, |word, n| {
+    for &byte in &word.to_le_bytes()[..n] {
+      out.push(byte);
+    }
+  })?;
+  out.finish();
+  Ok(size)
+}
@@ -2,12 +2,48 @@
 #![allow(unused_mut)]
 #![allow(non_camel_case_types)]
 #![allow(unsafe_op_in_unsafe_fn)]
+// The fallback code is what uses most of the crate-level helpers.
+#![cfg_attr(
+  all(target_arch = "arm", feature = "armv4t"),
+  allow(unused_imports, unused_macros)
+)]
 
 use core::mem::MaybeUninit;
 
 type mu_u8 = MaybeUninit<u8>;
 type mu_u16 = MaybeUninit<u16>;
 type mu_u32 = MaybeUninit<u32>;
+type mu_u64 = MaybeUninit<u64>;
+
+/// A `usize` with every byte set to `0x01`.
+const LO_BYTES: usize = usize::MAX / 0xFF;
+
+/// A `usize` with every byte set to `0x80`.
+const HI_BYTES: usize = LO_BYTES << 7;
+
+/// If any byte within `x` is zero.
+///
+/// Subtracting 1 from each byte only borrows out of a byte (setting its high
+/// bit) if that byte was zero, or if the byte below it borrowed. Masking with
+/// `!x` throws out bytes that already had their high bit set. This can give
+/// false positives for bytes *above* a zero byte, but never when there isn't
+/// a zero byte at all.
+#[inline(always)]
+#[allow(dead_code)]
+const fn has_zero_byte(x: usize) -> bool {
+  (x.wrapping_sub(LO_BYTES) & !x & HI_BYTES) != 0
+}
+
+/// Gives the `link_section` name for a function, using the prefix that the
+/// build script picked (see `build.rs`). Only use this behind
+/// `cfg_attr(place_<group>, ...)` for the function's group, since otherwise
+/// there's no prefix.
+#[cfg(link_section_prefix)]
+macro_rules! section_name {
+  ($name:literal) => {
+    concat!(env!("AEABI_FNS_LINK_SECTION_PREFIX"), $name)
+  };
+}
 
 /// This macro makes it easy to pick between `armv4t`-specific inline asm and
 /// the Rust-only fallback version.
@@ -32,6 +68,198 @@ macro_rules! cfg_armv4t {
   }
 }
 
+/// This macro makes it easy to pick between Thumb-2 inline asm (for targets
+/// such as ARMv7-M and ARMv7E-M) and the Rust-only fallback version.
+///
+/// It's intended to be used within the `no` branch of [`cfg_armv4t!`], so that
+/// the `armv4t` feature keeps priority when it's active.
+macro_rules! cfg_thumb2 {
+  (
+    yes: {
+      $($yes_tokens:tt)*
+    }
+    no: {
+      $($no_tokens:tt)*
+    }
+  ) => {
+    #[cfg(all(target_arch="arm", feature="thumb2"))]
+    {
+      $($yes_tokens)*
+    }
+    #[cfg(not(all(target_arch="arm", feature="thumb2")))]
+    {
+      $($no_tokens)*
+    }
+  }
+}
+
+/// On targets that always have 128-bit vector registers (SSE2 on `x86_64`,
+/// NEON on `aarch64`) this moves 64 bytes at a time and then 16 bytes at a time
+/// from `$src` to `$dest` going upward, for as long as `$count` is at least 16.
+/// All three variables are updated, and the caller's own loop should handle
+/// whatever remains.
+///
+/// Each chunk is fully loaded before any of it is stored, so this is fine with
+/// the same partial overlap that the forward functions allow.
+///
+/// It's only defined for those two targets, so each use needs the same `cfg`.
+/// Under Miri, which can't run inline asm, it does nothing.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+macro_rules! simd128_bulk_forward {
+  ($dest:ident, $src:ident, $count:ident) => {
+    #[cfg(all(target_arch = "x86_64", target_feature = "sse2", not(miri)))]
+    core::arch::asm! {
+      // note: labels made of only 0 and 1 can't be used with intel syntax
+      "sub     {count}, 64",
+      "jb      3f",
+      "2:",
+      "movdqu  {a}, xmmword ptr [{src}]",
+      "movdqu  {b}, xmmword ptr [{src} + 16]",
+      "movdqu  {c}, xmmword ptr [{src} + 32]",
+      "movdqu  {d}, xmmword ptr [{src} + 48]",
+      "movdqu  xmmword ptr [{dest}], {a}",
+      "movdqu  xmmword ptr [{dest} + 16], {b}",
+      "movdqu  xmmword ptr [{dest} + 32], {c}",
+      "movdqu  xmmword ptr [{dest} + 48], {d}",
+      "add     {src}, 64",
+      "add     {dest}, 64",
+      "sub     {count}, 64",
+      "jae     2b",
+      "3:",
+      "add     {count}, 64",
+      "sub     {count}, 16",
+      "jb      5f",
+      "4:",
+      "movdqu  {a}, xmmword ptr [{src}]",
+      "movdqu  xmmword ptr [{dest}], {a}",
+      "add     {src}, 16",
+      "add     {dest}, 16",
+      "sub     {count}, 16",
+      "jae     4b",
+      "5:",
+      "add     {count}, 16",
+      dest = inout(reg) $dest,
+      src = inout(reg) $src,
+      count = inout(reg) $count,
+      a = out(xmm_reg) _,
+      b = out(xmm_reg) _,
+      c = out(xmm_reg) _,
+      d = out(xmm_reg) _,
+      options(nostack)
+    }
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon", not(miri)))]
+    core::arch::asm! {
+      "subs    {count}, {count}, #64",
+      "b.lo    3f",
+      "2:",
+      "ldp     {a:q}, {b:q}, [{src}]",
+      "ldp     {c:q}, {d:q}, [{src}, #32]",
+      "add     {src}, {src}, #64",
+      "stp     {a:q}, {b:q}, [{dest}]",
+      "stp     {c:q}, {d:q}, [{dest}, #32]",
+      "add     {dest}, {dest}, #64",
+      "subs    {count}, {count}, #64",
+      "b.hs    2b",
+      "3:",
+      // count = (count + 64) - 16, and the carry is set if that's >= 0
+      "adds    {count}, {count}, #48",
+      "b.lo    5f",
+      "4:",
+      "ldr     {a:q}, [{src}], #16",
+      "str     {a:q}, [{dest}], #16",
+      "subs    {count}, {count}, #16",
+      "b.hs    4b",
+      "5:",
+      "add     {count}, {count}, #16",
+      dest = inout(reg) $dest,
+      src = inout(reg) $src,
+      count = inout(reg) $count,
+      a = out(vreg) _,
+      b = out(vreg) _,
+      c = out(vreg) _,
+      d = out(vreg) _,
+      options(nostack)
+    }
+  };
+}
+
+/// The backward version of [`simd128_bulk_forward!`], so `$dest` and `$src`
+/// are one-past-the-end pointers and they're adjusted *before* each copy.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+macro_rules! simd128_bulk_backward {
+  ($dest:ident, $src:ident, $count:ident) => {
+    #[cfg(all(target_arch = "x86_64", target_feature = "sse2", not(miri)))]
+    core::arch::asm! {
+      "sub     {count}, 64",
+      "jb      3f",
+      "2:",
+      "sub     {src}, 64",
+      "sub     {dest}, 64",
+      "movdqu  {a}, xmmword ptr [{src} + 48]",
+      "movdqu  {b}, xmmword ptr [{src} + 32]",
+      "movdqu  {c}, xmmword ptr [{src} + 16]",
+      "movdqu  {d}, xmmword ptr [{src}]",
+      "movdqu  xmmword ptr [{dest} + 48], {a}",
+      "movdqu  xmmword ptr [{dest} + 32], {b}",
+      "movdqu  xmmword ptr [{dest} + 16], {c}",
+      "movdqu  xmmword ptr [{dest}], {d}",
+      "sub     {count}, 64",
+      "jae     2b",
+      "3:",
+      "add     {count}, 64",
+      "sub     {count}, 16",
+      "jb      5f",
+      "4:",
+      "sub     {src}, 16",
+      "sub     {dest}, 16",
+      "movdqu  {a}, xmmword ptr [{src}]",
+      "movdqu  xmmword ptr [{dest}], {a}",
+      "sub     {count}, 16",
+      "jae     4b",
+      "5:",
+      "add     {count}, 16",
+      dest = inout(reg) $dest,
+      src = inout(reg) $src,
+      count = inout(reg) $count,
+      a = out(xmm_reg) _,
+      b = out(xmm_reg) _,
+      c = out(xmm_reg) _,
+      d = out(xmm_reg) _,
+      options(nostack)
+    }
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon", not(miri)))]
+    core::arch::asm! {
+      "subs    {count}, {count}, #64",
+      "b.lo    3f",
+      "2:",
+      "ldp     {a:q}, {b:q}, [{src}, #-32]",
+      "ldp     {c:q}, {d:q}, [{src}, #-64]!",
+      "stp     {a:q}, {b:q}, [{dest}, #-32]",
+      "stp     {c:q}, {d:q}, [{dest}, #-64]!",
+      "subs    {count}, {count}, #64",
+      "b.hs    2b",
+      "3:",
+      "adds    {count}, {count}, #48",
+      "b.lo    5f",
+      "4:",
+      "ldr     {a:q}, [{src}, #-16]!",
+      "str     {a:q}, [{dest}, #-16]!",
+      "subs    {count}, {count}, #16",
+      "b.hs    4b",
+      "5:",
+      "add     {count}, {count}, #16",
+      dest = inout(reg) $dest,
+      src = inout(reg) $src,
+      count = inout(reg) $count,
+      a = out(vreg) _,
+      b = out(vreg) _,
+      c = out(vreg) _,
+      d = out(vreg) _,
+      options(nostack)
+    }
+  };
+}
+
 // Note(Lokathor): Each individual function is a separate file for ease of
 // tabbed viewing, because they're not very visually distinct when scrolling up
 // and down within a single file.
@@ -53,3 +281,183 @@ pub use copy_u32_forward::copy_u32_forward;
 
 mod copy_u32_backward;
 pub use copy_u32_backward::copy_u32_backward;
+
+mod copy_u64_forward;
+pub use copy_u64_forward::copy_u64_forward;
+
+mod copy_u64_backward;
+pub use copy_u64_backward::copy_u64_backward;
+
+// These return where each copy stopped (like `mempcpy` does), so that copies
+// can be chained.
+
+mod copy_u8_forward_advance;
+pub use copy_u8_forward_advance::copy_u8_forward_advance;
+use copy_u8_forward_advance::copy_u8_forward_unchecked;
+
+mod copy_u8_backward_advance;
+pub use copy_u8_backward_advance::copy_u8_backward_advance;
+use copy_u8_backward_advance::copy_u8_backward_unchecked;
+
+mod copy_u16_forward_advance;
+pub use copy_u16_forward_advance::copy_u16_forward_advance;
+use copy_u16_forward_advance::copy_u16_forward_unchecked;
+
+mod copy_u16_backward_advance;
+pub use copy_u16_backward_advance::copy_u16_backward_advance;
+use copy_u16_backward_advance::copy_u16_backward_unchecked;
+
+mod copy_u32_forward_advance;
+pub use copy_u32_forward_advance::copy_u32_forward_advance;
+use copy_u32_forward_advance::copy_u32_forward_unchecked;
+
+mod copy_u32_backward_advance;
+pub use copy_u32_backward_advance::copy_u32_backward_advance;
+use copy_u32_backward_advance::copy_u32_backward_unchecked;
+
+mod copy_forward;
+pub use copy_forward::copy_forward;
+
+mod copy_backward;
+pub use copy_backward::copy_backward;
+
+mod copy_error;
+pub use copy_error::{CopyError, CopyPointer};
+
+mod contract;
+use contract::{
+  byte_count, check_copy_backward, check_copy_forward, check_swap,
+  check_whole_elements, validate_copy_backward, validate_copy_forward,
+};
+
+mod try_copy_forward;
+pub use try_copy_forward::try_copy_forward;
+
+mod try_copy_backward;
+pub use try_copy_backward::try_copy_backward;
+
+mod try_copy_u8_forward;
+pub use try_copy_u8_forward::try_copy_u8_forward;
+
+mod try_copy_u8_backward;
+pub use try_copy_u8_backward::try_copy_u8_backward;
+
+mod try_copy_u16_forward;
+pub use try_copy_u16_forward::try_copy_u16_forward;
+
+mod try_copy_u16_backward;
+pub use try_copy_u16_backward::try_copy_u16_backward;
+
+mod try_copy_u32_forward;
+pub use try_copy_u32_forward::try_copy_u32_forward;
+
+mod try_copy_u32_backward;
+pub use try_copy_u32_backward::try_copy_u32_backward;
+
+mod copy_u8_forward_replicating;
+pub use copy_u8_forward_replicating::copy_u8_forward_replicating;
+
+mod copy_forward_any;
+pub use copy_forward_any::copy_forward_any;
+
+mod copy_backward_any;
+pub use copy_backward_any::copy_backward_any;
+
+// These are `const fn` versions of the above, for use at compile time. Since
+// they're not meant for runtime use they don't get a `link_section`.
+
+mod copy_u8_forward_const;
+pub use copy_u8_forward_const::copy_u8_forward_const;
+
+mod copy_u8_backward_const;
+pub use copy_u8_backward_const::copy_u8_backward_const;
+
+mod copy_u16_forward_const;
+pub use copy_u16_forward_const::copy_u16_forward_const;
+
+mod copy_u16_backward_const;
+pub use copy_u16_backward_const::copy_u16_backward_const;
+
+mod copy_u32_forward_const;
+pub use copy_u32_forward_const::copy_u32_forward_const;
+
+mod copy_u32_backward_const;
+pub use copy_u32_backward_const::copy_u32_backward_const;
+
+mod copy_u16_swap_forward;
+pub use copy_u16_swap_forward::copy_u16_swap_forward;
+
+mod copy_u32_swap_forward;
+pub use copy_u32_swap_forward::copy_u32_swap_forward;
+
+mod swap_u8;
+pub use swap_u8::swap_u8;
+
+mod swap_u16;
+pub use swap_u16::swap_u16;
+
+mod swap_u32;
+pub use swap_u32::swap_u32;
+
+mod compare_u8;
+pub use compare_u8::compare_u8;
+
+mod compare_u16;
+pub use compare_u16::compare_u16;
+
+mod compare_u32;
+pub use compare_u32::compare_u32;
+
+mod fill_pattern;
+pub use fill_pattern::fill_pattern;
+
+mod decompress_error;
+pub use decompress_error::DecompressError;
+
+mod read_bios_header;
+use read_bios_header::read_bios_header;
+
+mod huff_decode;
+use huff_decode::huff_decode;
+
+mod halfword_writer;
+use halfword_writer::HalfwordWriter;
+
+mod lz77_decompress;
+pub use lz77_decompress::lz77_decompress;
+
+mod lz77_decompress_u16;
+pub use lz77_decompress_u16::lz77_decompress_u16;
+
+mod rl_decompress;
+pub use rl_decompress::rl_decompress;
+
+mod rl_decompress_u16;
+pub use rl_decompress_u16::rl_decompress_u16;
+
+mod huff_decompress;
+pub use huff_decompress::huff_decompress;
+
+mod huff_decompress_u16;
+pub use huff_decompress_u16::huff_decompress_u16;
+
+mod bit_unpack;
+pub use bit_unpack::{bit_unpack, BitUnpackInfo};
+
+// These are the C library functions. With the `c_symbols` feature they're
+// exported under their C names, otherwise they're normal Rust functions.
+
+mod memcmp;
+pub use memcmp::memcmp;
+
+mod bcmp;
+pub use bcmp::bcmp;
+
+mod strlen;
+pub use strlen::strlen;
+
+mod memchr;
+pub use memchr::memchr;
+
+mod memrchr;
+pub use memrchr::memrchr;
@@ -0,0 +1,77 @@
+use crate::*;
+
+/// Decompresses GBA BIOS LZ77 data (the `0x10` format of `LZ77UnCompWram`)
+/// from `src` into `dest`, and gives the decompressed size in bytes.
+///
+/// The data starts with a 4-byte header, which is `0x10` in the low byte and
+/// the decompressed size in the upper 24 bits (little-endian). After that, each
+/// flag byte says (from its highest bit down) whether each of the next 8 blocks
+/// is a literal byte (0) or a 2-byte back-reference (1). A back-reference gives
+/// a length of 3 to 18 bytes and a distance of 1 to 4096 bytes back into the
+/// output, and is done with [`copy_u8_forward_replicating`], so it can overlap
+/// the bytes it's writing.
+///
+/// Only the first `size` bytes of `dest` are written. If the last
+/// back-reference goes past `size` it's cut short, which gives the same bytes
+/// as the BIOS (which doesn't cut it short).
+///
+/// This writes single bytes, so it can't be used with the GBA's VRAM. For that,
+/// see [`lz77_decompress_u16`].
+///
+/// ## Failure
+/// * [`DecompressError::WrongFormat`] if the header's first byte isn't `0x1?`
+///   (the low 4 bits aren't checked, like with the BIOS).
+/// * [`DecompressError::OutputTooSmall`] if `dest` is shorter than the size.
+/// * [`DecompressError::Truncated`] if `src` ends before the output is done.
+/// * [`DecompressError::BadReference`] if a back-reference points to before the
+///   start of `dest`.
+///
+/// When there's an error partway through, `dest` is left with the part that
+/// was already decompressed.
+#[inline]
+#[cfg_attr(place_decoders, link_section = section_name!("lz77_decompress"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub fn lz77_decompress(
+  src: &[u8], dest: &mut [u8],
+) -> Result<usize, DecompressError> {
+  let size = read_bios_header(src, 0x10)?;
+  let Some(dest) = dest.get_mut(..size) else {
+    return Err(DecompressError::OutputTooSmall { needed: size });
+  };
+  let mut input = src[4..].iter().copied();
+  let mut next = || input.next().ok_or(DecompressError::Truncated);
+  let mut pos = 0;
+  while pos < size {
+    let flags = next()?;
+    for bit in (0..8).rev() {
+      if pos == size {
+        break;
+      }
+      if flags & (1 << bit) == 0 {
+        dest[pos] = next()?;
+        pos += 1;
+      } else {
+        let (b0, b1) = (next()?, next()?);
+        let len = usize::from(b0 >> 4) + 3;
+        let distance = (usize::from(b0 & 0xF) << 8 | usize::from(b1)) + 1;
+        if distance > pos {
+          return Err(DecompressError::BadReference);
+        }
+        let len = len.min(size - pos);
+        unsafe {
+          let p = dest.as_mut_ptr();
+          copy_u8_forward_replicating(
+            p.add(pos).cast(),
+            p.add(pos - distance).cast(),
+            len,
+          );
+        }
+        pos += len;
+      }
+    }
+  }
+  Ok(size)
+}
@@ -0,0 +1,59 @@
+use crate::*;
+
+/// Like [`lz77_decompress`], but only ever writes whole `u16` values to `dest`
+/// (like `LZ77UnCompVram`), since the GBA's VRAM ignores or mangles single byte
+/// writes.
+///
+/// The output bytes are put together in pairs, so that `dest` has the same
+/// bytes in memory as [`lz77_decompress`] would give. If the size is odd then
+/// the last byte is combined with the high byte already in `dest` (reading
+/// VRAM a byte at a time is fine). The size returned is still in bytes.
+///
+/// Unlike the BIOS, a back-reference with a distance of 1 works here.
+///
+/// ## Failure
+/// * The same as [`lz77_decompress`], except `dest` needs `size.div_ceil(2)`
+///   elements.
+#[inline]
+#[cfg_attr(place_decoders, link_section = section_name!("lz77_decompress_u16"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub fn lz77_decompress_u16(
+  src: &[u8], dest: &mut [u16],
+) -> Result<usize, DecompressError> {
+  let size = read_bios_header(src, 0x10)?;
+  let Some(dest) = dest.get_mut(..size.div_ceil(2)) else {
+    return Err(DecompressError::OutputTooSmall { needed: size.div_ceil(2) });
+  };
+  let mut input = src[4..].iter().copied();
+  let mut next = || input.next().ok_or(DecompressError::Truncated);
+  let mut out = HalfwordWriter::new(dest);
+  while out.pos() < size {
+    let flags = next()?;
+    for bit in (0..8).rev() {
+      if out.pos() == size {
+        break;
+      }
+      if flags & (1 << bit) == 0 {
+        out.push(next()?);
+      } else {
+        let (b0, b1) = (next()?, next()?);
+        let len = usize::from(b0 >> 4) + 3;
+        let distance = (usize::from(b0 & 0xF) << 8 | usize::from(b1)) + 1;
+        if distance > out.pos() {
+          return Err(DecompressError::BadReference);
+        }
+        // Like `copy_u8_forward_replicating`, each byte is read after the one
+        // before it is written.
+        for _ in 0..len.min(size - out.pos()) {
+          let byte = out.get(out.pos() - distance);
+          out.push(byte);
+        }
+      }
+    }
+  }
+  out.finish();
+  Ok(size)
+}
@@ -0,0 +1,110 @@
+use crate::*;
+
+/// The C `memchr` function (exported under that name with the `c_symbols`
+/// feature).
+///
+/// Searches the `count` bytes at `ptr` for `byte` (converted to `u8`), going
+/// upward in address value. Returns a pointer to the first match, or null if
+/// there's no match.
+///
+/// Bytes are checked one at a time until `ptr` is aligned, then a whole word at
+/// a time, and then one at a time again for the final bytes.
+///
+/// ## Safety
+/// * If `count` is zero, the `ptr` is not accessed, and it can even be invalid
+///   or null.
+/// * If `count` is non-zero, then `ptr` must be valid for `count` initialized
+///   bytes forward.
+#[cfg_attr(not(feature = "c_symbols"), inline)]
+#[cfg_attr(feature = "c_symbols", no_mangle)]
+#[cfg_attr(place_strings, link_section = section_name!("memchr"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub unsafe extern "C" fn memchr(
+  mut ptr: *const u8, byte: i32, mut count: usize,
+) -> *mut u8 {
+  let byte = byte as u8;
+  cfg_armv4t! {
+    yes: {
+      core::arch::asm! {
+        // while `ptr` isn't aligned, check single bytes
+        "1:",
+        "tst     {ptr}, #3",
+        "beq     2f",
+        "subs    {count}, {count}, #1",
+        "movlt   {ptr}, #0",
+        "blt     5f",
+        "ldrb    {w}, [{ptr}], #1",
+        // the top byte of the pattern is the byte we're looking for
+        "cmp     {w}, {pattern}, lsr #24",
+        "bne     1b",
+        "sub     {ptr}, {ptr}, #1",
+        "b       5f",
+
+        // check whole words, where a match becomes a zero byte after the `eor`
+        "2:",
+        "subs    {count}, {count}, #4",
+        "blt     3f",
+        "ldr     {w}, [{ptr}], #4",
+        "eor     {w}, {w}, {pattern}",
+        "sub     {t}, {w}, {lo_bytes}",
+        "bic     {t}, {t}, {w}",
+        "tst     {t}, {lo_bytes}, lsl #7",
+        "beq     2b",
+        // The word has a match, so back up and find it. The count is set so
+        // that there's 4 bytes left after the `add` below.
+        "sub     {ptr}, {ptr}, #4",
+        "mov     {count}, #0",
+        "3:",
+        "add     {count}, {count}, #4",
+
+        // check the last few single bytes
+        "4:",
+        "subs    {count}, {count}, #1",
+        "movlt   {ptr}, #0",
+        "blt     5f",
+        "ldrb    {w}, [{ptr}], #1",
+        "cmp     {w}, {pattern}, lsr #24",
+        "bne     4b",
+        "sub     {ptr}, {ptr}, #1",
+        "5:",
+        ptr = inout(reg) ptr,
+        count = inout(reg) count => _,
+        pattern = in(reg) u32::from(byte) * 0x0101_0101,
+        lo_bytes = in(reg) 0x0101_0101_u32,
+        w = out(reg) _,
+        t = out(reg) _,
+        options(nostack, readonly)
+      }
+      ptr.cast_mut()
+    }
+    no: {
+      const WORD: usize = core::mem::size_of::<usize>();
+      let pattern = LO_BYTES * usize::from(byte);
+      while count > 0 && !(ptr as usize).is_multiple_of(WORD) {
+        if *ptr == byte {
+          return ptr.cast_mut();
+        }
+        ptr = ptr.add(1);
+        count -= 1;
+      }
+      while count >= WORD {
+        if has_zero_byte(ptr.cast::<usize>().read() ^ pattern) {
+          break;
+        }
+        ptr = ptr.add(WORD);
+        count -= WORD;
+      }
+      while count > 0 {
+        if *ptr == byte {
+          return ptr.cast_mut();
+        }
+        ptr = ptr.add(1);
+        count -= 1;
+      }
+      core::ptr::null_mut()
+    }
+  }
+}
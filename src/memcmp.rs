@@ -0,0 +1,27 @@
+use crate::*;
+
+/// The C `memcmp` function (exported under that name with the `c_symbols`
+/// feature).
+///
+/// This picks [`compare_u32`], [`compare_u16`], or [`compare_u8`] based on the
+/// alignment of both pointers.
+///
+/// With `c_symbols` this is never inlined, so that [`bcmp`] can share it.
+///
+/// ## Safety
+/// * The same as [`compare_u8`].
+#[cfg_attr(not(feature = "c_symbols"), inline)]
+#[cfg_attr(feature = "c_symbols", no_mangle, inline(never))]
+#[cfg_attr(place_compares, link_section = section_name!("memcmp"))]
+pub unsafe extern "C" fn memcmp(
+  a: *const u8, b: *const u8, count: usize,
+) -> i32 {
+  let align_bits = (a as usize) | (b as usize);
+  if align_bits & 0b11 == 0 {
+    compare_u32(a.cast(), b.cast(), count)
+  } else if align_bits & 0b1 == 0 {
+    compare_u16(a.cast(), b.cast(), count)
+  } else {
+    compare_u8(a, b, count)
+  }
+}
@@ -0,0 +1,107 @@
+use crate::*;
+
+/// The `memrchr` function (a GNU extension to C, exported under that name with
+/// the `c_symbols` feature).
+///
+/// Searches the `count` bytes at `ptr` for `byte` (converted to `u8`), going
+/// downward in address value from the end of the region. Returns a pointer to
+/// the last match, or null if there's no match.
+///
+/// Like C, this takes a pointer to the *start* of the region, unlike the
+/// crate's `_backward` functions. Otherwise it works like [`memchr`] in
+/// reverse.
+///
+/// ## Safety
+/// * If `count` is zero, the `ptr` is not accessed, and it can even be invalid
+///   or null.
+/// * If `count` is non-zero, then `ptr` must be valid for `count` initialized
+///   bytes forward.
+#[cfg_attr(not(feature = "c_symbols"), inline)]
+#[cfg_attr(feature = "c_symbols", no_mangle)]
+#[cfg_attr(place_strings, link_section = section_name!("memrchr"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub unsafe extern "C" fn memrchr(
+  ptr: *const u8, byte: i32, mut count: usize,
+) -> *mut u8 {
+  let byte = byte as u8;
+  // IMPORTANT: we walk `end` downward, adjusting it *before* each access, the
+  // same as the backward copy loops do.
+  let mut end = ptr.wrapping_add(count);
+  cfg_armv4t! {
+    yes: {
+      // The loop reasoning here is similar to `memchr`
+      core::arch::asm! {
+        "1:",
+        "tst     {end}, #3",
+        "beq     2f",
+        "subs    {count}, {count}, #1",
+        "movlt   {end}, #0",
+        "blt     5f",
+        "ldrb    {w}, [{end}, #-1]!",
+        "cmp     {w}, {pattern}, lsr #24",
+        "bne     1b",
+        "b       5f",
+
+        "2:",
+        "subs    {count}, {count}, #4",
+        "blt     3f",
+        "ldr     {w}, [{end}, #-4]!",
+        "eor     {w}, {w}, {pattern}",
+        "sub     {t}, {w}, {lo_bytes}",
+        "bic     {t}, {t}, {w}",
+        "tst     {t}, {lo_bytes}, lsl #7",
+        "beq     2b",
+        "add     {end}, {end}, #4",
+        "mov     {count}, #0",
+        "3:",
+        "add     {count}, {count}, #4",
+
+        "4:",
+        "subs    {count}, {count}, #1",
+        "movlt   {end}, #0",
+        "blt     5f",
+        "ldrb    {w}, [{end}, #-1]!",
+        "cmp     {w}, {pattern}, lsr #24",
+        "bne     4b",
+        "5:",
+        end = inout(reg) end,
+        count = inout(reg) count => _,
+        pattern = in(reg) u32::from(byte) * 0x0101_0101,
+        lo_bytes = in(reg) 0x0101_0101_u32,
+        w = out(reg) _,
+        t = out(reg) _,
+        options(nostack, readonly)
+      }
+      end.cast_mut()
+    }
+    no: {
+      const WORD: usize = core::mem::size_of::<usize>();
+      let pattern = LO_BYTES * usize::from(byte);
+      while count > 0 && !(end as usize).is_multiple_of(WORD) {
+        end = end.sub(1);
+        if *end == byte {
+          return end.cast_mut();
+        }
+        count -= 1;
+      }
+      while count >= WORD {
+        if has_zero_byte(end.sub(WORD).cast::<usize>().read() ^ pattern) {
+          break;
+        }
+        end = end.sub(WORD);
+        count -= WORD;
+      }
+      while count > 0 {
+        end = end.sub(1);
+        if *end == byte {
+          return end.cast_mut();
+        }
+        count -= 1;
+      }
+      core::ptr::null_mut()
+    }
+  }
+}
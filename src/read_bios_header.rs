@@ -0,0 +1,19 @@
+use crate::*;
+
+/// Reads the 4-byte header that the GBA BIOS decompression formats start with,
+/// and gives the decompressed size in bytes.
+///
+/// The high 4 bits of the first byte are the format's type, which must match
+/// the high 4 bits of `kind`, and the other 24 bits are the size.
+#[inline(always)]
+pub(crate) fn read_bios_header(
+  src: &[u8], kind: u8,
+) -> Result<usize, DecompressError> {
+  let Some(&header) = src.first_chunk::<4>() else {
+    return Err(DecompressError::Truncated);
+  };
+  if header[0] & 0xF0 != kind & 0xF0 {
+    return Err(DecompressError::WrongFormat { found: header[0] });
+  }
+  Ok((u32::from_le_bytes(header) >> 8) as usize)
+}
@@ -0,0 +1,67 @@
+use crate::*;
+
+/// Decompresses GBA BIOS run-length data (the `0x30` format of `RLUnCompWram`)
+/// from `src` into `dest`, and gives the decompressed size in bytes.
+///
+/// The data starts with a 4-byte header, which is `0x30` in the low byte and
+/// the decompressed size in the upper 24 bits (little-endian). After that, each
+/// flag byte is either:
+/// * `0b0nnn_nnnn`: the next `n + 1` bytes are copied as they are, which is
+///   done with [`copy_forward_any`].
+/// * `0b1nnn_nnnn`: the next byte is repeated `n + 3` times, which is done with
+///   [`fill_pattern`].
+///
+/// Only the first `size` bytes of `dest` are written, and the last block is cut
+/// short if it goes past `size`.
+///
+/// This writes single bytes, so it can't be used with the GBA's VRAM. For that,
+/// see [`rl_decompress_u16`].
+///
+/// ## Failure
+/// * [`DecompressError::WrongFormat`] if the header's first byte isn't `0x3?`
+///   (the low 4 bits aren't checked, like with the BIOS).
+/// * [`DecompressError::OutputTooSmall`] if `dest` is shorter than the size.
+/// * [`DecompressError::Truncated`] if `src` ends before the output is done.
+///
+/// When there's an error partway through, `dest` is left with the part that
+/// was already decompressed.
+#[inline]
+#[cfg_attr(place_decoders, link_section = section_name!("rl_decompress"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub fn rl_decompress(
+  src: &[u8], dest: &mut [u8],
+) -> Result<usize, DecompressError> {
+  let size = read_bios_header(src, 0x30)?;
+  let Some(dest) = dest.get_mut(..size) else {
+    return Err(DecompressError::OutputTooSmall { needed: size });
+  };
+  let mut rest = &src[4..];
+  let mut pos = 0;
+  while pos < size {
+    let Some((&flag, after_flag)) = rest.split_first() else {
+      return Err(DecompressError::Truncated);
+    };
+    let p = unsafe { dest.as_mut_ptr().add(pos) };
+    let len;
+    if flag & 0x80 == 0 {
+      len = (usize::from(flag & 0x7F) + 1).min(size - pos);
+      let Some((bytes, after)) = after_flag.split_at_checked(len) else {
+        return Err(DecompressError::Truncated);
+      };
+      unsafe { copy_forward_any(p.cast(), bytes.as_ptr().cast(), len) };
+      rest = after;
+    } else {
+      len = (usize::from(flag & 0x7F) + 3).min(size - pos);
+      let Some((&byte, after)) = after_flag.split_first() else {
+        return Err(DecompressError::Truncated);
+      };
+      unsafe { fill_pattern(p.cast(), &[byte], len) };
+      rest = after;
+    }
+    pos += len;
+  }
+  Ok(size)
+}
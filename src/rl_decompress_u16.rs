@@ -0,0 +1,55 @@
+use crate::*;
+
+/// Like [`rl_decompress`], but only ever writes whole `u16` values to `dest`
+/// (like `RLUnCompVram`), since the GBA's VRAM ignores or mangles single byte
+/// writes.
+///
+/// The output bytes are put together in pairs, so that `dest` has the same
+/// bytes in memory as [`rl_decompress`] would give. If the size is odd then
+/// the last byte is combined with the high byte already in `dest`. The size
+/// returned is still in bytes.
+///
+/// The middle of a long run is stored a word at a time with [`fill_pattern`],
+/// once the output is aligned to 4.
+///
+/// ## Failure
+/// * The same as [`rl_decompress`], except `dest` needs `size.div_ceil(2)`
+///   elements.
+#[inline]
+#[cfg_attr(place_decoders, link_section = section_name!("rl_decompress_u16"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub fn rl_decompress_u16(
+  src: &[u8], dest: &mut [u16],
+) -> Result<usize, DecompressError> {
+  let size = read_bios_header(src, 0x30)?;
+  let Some(dest) = dest.get_mut(..size.div_ceil(2)) else {
+    return Err(DecompressError::OutputTooSmall { needed: size.div_ceil(2) });
+  };
+  let mut input = src[4..].iter().copied();
+  let mut next = || input.next().ok_or(DecompressError::Truncated);
+  let mut out = HalfwordWriter::new(dest);
+  while out.pos() < size {
+    let flag = next()?;
+    let is_run = flag & 0x80 != 0;
+    let (mut len, byte) = if is_run {
+      ((usize::from(flag & 0x7F) + 3).min(size - out.pos()), next()?)
+    } else {
+      ((usize::from(flag & 0x7F) + 1).min(size - out.pos()), 0)
+    };
+    while len > 0 {
+      if is_run && len >= 4 && out.is_word_aligned() {
+        let words = len & !0b11;
+        out.fill_words(byte, words);
+        len -= words;
+        continue;
+      }
+      out.push(if is_run { byte } else { next()? });
+      len -= 1;
+    }
+  }
+  out.finish();
+  Ok(size)
+}
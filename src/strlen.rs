@@ -0,0 +1,70 @@
+/// The C `strlen` function (exported under that name with the `c_symbols`
+/// feature).
+///
+/// Gives the number of bytes before the first zero byte at `ptr`.
+///
+/// With the `armv4t` asm, bytes are checked one at a time until `ptr` is
+/// aligned, then a whole word at a time. When a word has the zero byte in it,
+/// that word is checked one byte at a time. Otherwise every byte is checked one
+/// at a time, since Rust code can't read past the end of the string's
+/// allocation.
+///
+/// ## Safety
+/// * `ptr` must be valid for reads up to and including a zero byte.
+/// * With the `armv4t` asm, aligned words are read, so up to 3 bytes after the
+///   zero byte may also be read. An aligned word can't cross into another page
+///   or memory region, so this is harmless on real hardware. The other versions
+///   never read past the zero byte.
+#[cfg_attr(not(feature = "c_symbols"), inline)]
+#[cfg_attr(feature = "c_symbols", no_mangle)]
+#[cfg_attr(place_strings, link_section = section_name!("strlen"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub unsafe extern "C" fn strlen(ptr: *const u8) -> usize {
+  let mut p = ptr;
+  cfg_armv4t! {
+    yes: {
+      // The loop reasoning here is similar to `memchr`, but there's no count.
+      core::arch::asm! {
+        "1:",
+        "tst     {p}, #3",
+        "beq     2f",
+        "ldrb    {w}, [{p}], #1",
+        "cmp     {w}, #0",
+        "bne     1b",
+        "b       4f",
+
+        "2:",
+        "ldr     {w}, [{p}], #4",
+        "sub     {t}, {w}, {lo_bytes}",
+        "bic     {t}, {t}, {w}",
+        "tst     {t}, {lo_bytes}, lsl #7",
+        "beq     2b",
+        "sub     {p}, {p}, #4",
+
+        "3:",
+        "ldrb    {w}, [{p}], #1",
+        "cmp     {w}, #0",
+        "bne     3b",
+        "4:",
+        p = inout(reg) p,
+        lo_bytes = in(reg) 0x0101_0101_u32,
+        w = out(reg) _,
+        t = out(reg) _,
+        options(nostack, readonly)
+      }
+      // the loops leave `p` one past the zero byte
+      p.offset_from(ptr) as usize - 1
+    }
+    no: {
+      // volatile for the same reason as in `copy_u8_forward_advance`, since
+      // LLVM can also recognize this loop as a `strlen` call.
+      while p.read_volatile() != 0 {
+        p = p.add(1);
+      }
+      p.offset_from(ptr) as usize
+    }
+  }
+}
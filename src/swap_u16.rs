@@ -0,0 +1,88 @@
+use crate::*;
+
+/// Swaps `count` bytes between `a` and `b`, going upward in address value.
+///
+/// Swaps are done in 2-byte chunks as much as possible. If the number of bytes
+/// to swap is odd then the last byte will be done using a 1-byte swap.
+///
+/// ## Safety
+/// * If `count` is zero, the `a` and `b` pointers are not accessed, and they
+///   can even be invalid or null.
+/// * If `count` is non-zero, then both `a` and `b` must be aligned, valid for
+///   `count` bytes forward, and either the regions are entirely disjoint, or
+///   `a` equals `b` (which has no effect).
+/// * `count` may not exceed `isize::MAX as usize`. (All Rust allocations
+///   already follow this rule, but perhaps it's worth stating that it is an
+///   assumption of the function.)
+#[inline]
+#[cfg_attr(place_copies, link_section = section_name!("swap_u16"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub unsafe extern "C" fn swap_u16(
+  mut a: *mut mu_u16, mut b: *mut mu_u16, mut count: usize,
+) {
+  check_swap("swap_u16", a as usize, b as usize, count, 2);
+  cfg_armv4t! {
+    yes: {
+      // The loop reasoning here is similar to `copy_u8_forward_advance`
+      core::arch::asm! {
+        "1:",
+        "subs    {count}, {count}, #2",
+        "ldrhge  {ta}, [{a}]",
+        "ldrhge  {tb}, [{b}]",
+        "strhge  {tb}, [{a}], #2",
+        "strhge  {ta}, [{b}], #2",
+        "bgt     1b",
+        a = inout(reg) a,
+        b = inout(reg) b,
+        count = inout(reg) count,
+        ta = out(reg) _,
+        tb = out(reg) _,
+        options(nostack)
+      }
+    }
+    no: {
+      cfg_thumb2! {
+        yes: {
+          // Same as the `armv4t` loop, but with an IT block.
+          core::arch::asm! {
+            "1:",
+            "subs    {count}, {count}, #2",
+            "itttt   ge",
+            "ldrhge  {ta}, [{a}]",
+            "ldrhge  {tb}, [{b}]",
+            "strhge  {tb}, [{a}], #2",
+            "strhge  {ta}, [{b}], #2",
+            "bgt     1b",
+            a = inout(reg) a,
+            b = inout(reg) b,
+            count = inout(reg) count,
+            ta = out(reg) _,
+            tb = out(reg) _,
+            options(nostack)
+          }
+        }
+        no: {
+          // volatile for the same reason as in `copy_u8_forward_advance`
+          while count >= 2 {
+            let temp = a.read_volatile();
+            a.write_volatile(b.read_volatile());
+            b.write_volatile(temp);
+            a = a.add(1);
+            b = b.add(1);
+            count -= 2;
+          }
+        }
+      }
+    }
+  }
+  // The ASM loop will always underflow the `count` value, so we do a bit test
+  // to check to test for when there's a 1-byte swap at the end.
+  if (count & 1) != 0 {
+    let temp = *a.cast::<mu_u8>();
+    *a.cast::<mu_u8>() = *b.cast::<mu_u8>();
+    *b.cast::<mu_u8>() = temp;
+  }
+}
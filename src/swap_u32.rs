@@ -0,0 +1,119 @@
+use crate::*;
+
+/// Swaps `count` bytes between `a` and `b`, going upward in address value.
+///
+/// Swaps are done in 4-byte chunks as much as possible. If the number of bytes
+/// to swap is not a multiple of 4 then the last portion will be done using a
+/// 2-byte and/or 1-byte swap.
+///
+/// ## Safety
+/// * If `count` is zero, the `a` and `b` pointers are not accessed, and they
+///   can even be invalid or null.
+/// * If `count` is non-zero, then both `a` and `b` must be aligned, valid for
+///   `count` bytes forward, and either the regions are entirely disjoint, or
+///   `a` equals `b` (which has no effect).
+/// * `count` may not exceed `isize::MAX as usize`. (All Rust allocations
+///   already follow this rule, but perhaps it's worth stating that it is an
+///   assumption of the function.)
+#[inline]
+#[cfg_attr(place_copies, link_section = section_name!("swap_u32"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub unsafe extern "C" fn swap_u32(
+  mut a: *mut mu_u32, mut b: *mut mu_u32, mut count: usize,
+) {
+  check_swap("swap_u32", a as usize, b as usize, count, 4);
+  cfg_armv4t! {
+    yes: {
+      core::arch::asm! {
+        // The loop reasoning here is similar to `copy_u8_forward_advance`
+        "1:",
+        "subs    {count}, {count}, #4",
+        "ldrge   {ta}, [{a}]",
+        "ldrge   {tb}, [{b}]",
+        "strge   {tb}, [{a}], #4",
+        "strge   {ta}, [{b}], #4",
+        "bgt     1b",
+
+        // Then the same bit 1 and bit 0 tails as `copy_u32_forward_advance`.
+        "lsls    {ta}, {count}, #31",
+        "ldrhcs  {ta}, [{a}]",
+        "ldrhcs  {tb}, [{b}]",
+        "strhcs  {tb}, [{a}], #2",
+        "strhcs  {ta}, [{b}], #2",
+        "ldrbmi  {ta}, [{a}]",
+        "ldrbmi  {tb}, [{b}]",
+        "strbmi  {tb}, [{a}], #1",
+        "strbmi  {ta}, [{b}], #1",
+
+        a = inout(reg) a => _,
+        b = inout(reg) b => _,
+        count = inout(reg) count => _,
+        ta = out(reg) _,
+        tb = out(reg) _,
+        options(nostack)
+      }
+    }
+    no: {
+      cfg_thumb2! {
+        yes: {
+          // Same as the `armv4t` code, but with IT blocks.
+          core::arch::asm! {
+            "1:",
+            "subs    {count}, {count}, #4",
+            "itttt   ge",
+            "ldrge   {ta}, [{a}]",
+            "ldrge   {tb}, [{b}]",
+            "strge   {tb}, [{a}], #4",
+            "strge   {ta}, [{b}], #4",
+            "bgt     1b",
+
+            "lsls    {ta}, {count}, #31",
+            "itttt   cs",
+            "ldrhcs  {ta}, [{a}]",
+            "ldrhcs  {tb}, [{b}]",
+            "strhcs  {tb}, [{a}], #2",
+            "strhcs  {ta}, [{b}], #2",
+            "itttt   mi",
+            "ldrbmi  {ta}, [{a}]",
+            "ldrbmi  {tb}, [{b}]",
+            "strbmi  {tb}, [{a}], #1",
+            "strbmi  {ta}, [{b}], #1",
+
+            a = inout(reg) a => _,
+            b = inout(reg) b => _,
+            count = inout(reg) count => _,
+            ta = out(reg) _,
+            tb = out(reg) _,
+            options(nostack)
+          }
+        }
+        no: {
+          // volatile for the same reason as in `copy_u8_forward_advance`
+          while count >= 4 {
+            let temp = a.read_volatile();
+            a.write_volatile(b.read_volatile());
+            b.write_volatile(temp);
+            a = a.add(1);
+            b = b.add(1);
+            count -= 4;
+          }
+          if (count & 0b10) != 0 {
+            let temp = *a.cast::<mu_u16>();
+            *a.cast::<mu_u16>() = *b.cast::<mu_u16>();
+            *b.cast::<mu_u16>() = temp;
+            a = a.byte_add(2);
+            b = b.byte_add(2);
+          }
+          if (count & 1) != 0 {
+            let temp = *a.cast::<mu_u8>();
+            *a.cast::<mu_u8>() = *b.cast::<mu_u8>();
+            *b.cast::<mu_u8>() = temp;
+          }
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,81 @@
+use crate::*;
+
+/// Swaps `count` bytes between `a` and `b`, going upward in address value.
+///
+/// Swaps are done one byte at a time.
+///
+/// ## Safety
+/// * If `count` is zero, the `a` and `b` pointers are not accessed, and they
+///   can even be invalid or null.
+/// * If `count` is non-zero, then both `a` and `b` must be valid for `count`
+///   bytes forward, and either the regions are entirely disjoint, or `a` equals
+///   `b` (which has no effect).
+/// * `count` may not exceed `isize::MAX as usize`. (All Rust allocations
+///   already follow this rule, but perhaps it's worth stating that it is an
+///   assumption of the function.)
+#[inline]
+#[cfg_attr(place_copies, link_section = section_name!("swap_u8"))]
+#[cfg_attr(
+  all(target_arch = "arm", target_feature = "thumb-mode", feature = "armv4t"),
+  instruction_set(arm::a32)
+)]
+pub unsafe extern "C" fn swap_u8(
+  mut a: *mut mu_u8, mut b: *mut mu_u8, mut count: usize,
+) {
+  check_swap("swap_u8", a as usize, b as usize, count, 1);
+  cfg_armv4t! {
+    yes: {
+      // The loop reasoning here is similar to `copy_u8_forward_advance`, but
+      // each iteration loads from both regions before storing to either one.
+      core::arch::asm! {
+        "1:",
+        "subs    {count}, {count}, #1",
+        "ldrbge  {ta}, [{a}]",
+        "ldrbge  {tb}, [{b}]",
+        "strbge  {tb}, [{a}], #1",
+        "strbge  {ta}, [{b}], #1",
+        "bgt     1b",
+        a = inout(reg) a => _,
+        b = inout(reg) b => _,
+        count = inout(reg) count => _,
+        ta = out(reg) _,
+        tb = out(reg) _,
+        options(nostack)
+      }
+    }
+    no: {
+      cfg_thumb2! {
+        yes: {
+          // Same as the `armv4t` loop, but with an IT block.
+          core::arch::asm! {
+            "1:",
+            "subs    {count}, {count}, #1",
+            "itttt   ge",
+            "ldrbge  {ta}, [{a}]",
+            "ldrbge  {tb}, [{b}]",
+            "strbge  {tb}, [{a}], #1",
+            "strbge  {ta}, [{b}], #1",
+            "bgt     1b",
+            a = inout(reg) a => _,
+            b = inout(reg) b => _,
+            count = inout(reg) count => _,
+            ta = out(reg) _,
+            tb = out(reg) _,
+            options(nostack)
+          }
+        }
+        no: {
+          // volatile for the same reason as in `copy_u8_forward_advance`
+          while count >= 1 {
+            let temp = a.read_volatile();
+            a.write_volatile(b.read_volatile());
+            b.write_volatile(temp);
+            a = a.add(1);
+            b = b.add(1);
+            count -= 1;
+          }
+        }
+      }
+    }
+  }
+}
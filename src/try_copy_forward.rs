@@ -0,0 +1,32 @@
+use crate::*;
+
+/// Like [`copy_forward`], but first checks the rules of its safety contract
+/// that can be checked from the pointers alone, and gives an error instead of
+/// copying if any of them are broken.
+///
+/// ## Failure
+/// * [`CopyError::Null`] if either pointer is null.
+/// * [`CopyError::Misaligned`] if either pointer isn't aligned for `T`.
+/// * [`CopyError::LengthOverflow`] if `count * size_of::<T>()` overflows or
+///   exceeds `isize::MAX`, or a region goes past the end of the address space.
+/// * [`CopyError::BadOverlap`] if the regions partly overlap with `src` less
+///   than `dest`.
+///
+/// If `count` is zero or `T` is zero-sized this always succeeds, and nothing is
+/// checked or accessed.
+///
+/// ## Safety
+/// * If the checks pass, both `src` and `dest` must be valid for `count` values
+///   of `T` forward.
+#[inline]
+pub unsafe fn try_copy_forward<T>(
+  dest: *mut T, src: *const T, count: usize,
+) -> Result<(), CopyError> {
+  let Some(bytes) = count.checked_mul(core::mem::size_of::<T>()) else {
+    return Err(CopyError::LengthOverflow);
+  };
+  let align = core::mem::align_of::<T>();
+  validate_copy_forward(dest as usize, src as usize, bytes, align)?;
+  copy_forward(dest, src, count);
+  Ok(())
+}
@@ -0,0 +1,27 @@
+use crate::*;
+
+/// Like [`copy_u16_forward`], but first checks the rules of its safety contract
+/// that can be checked from the pointers alone, and gives an error instead of
+/// copying if any of them are broken.
+///
+/// ## Failure
+/// * [`CopyError::Null`] if either pointer is null.
+/// * [`CopyError::Misaligned`] if either pointer isn't aligned to 2.
+/// * [`CopyError::LengthOverflow`] if `count` exceeds `isize::MAX`, or a region
+///   goes past the end of the address space.
+/// * [`CopyError::BadOverlap`] if the regions partly overlap with `src` less
+///   than `dest`.
+///
+/// If `count` is zero this always succeeds, and nothing is checked or accessed.
+///
+/// ## Safety
+/// * If the checks pass, both `src` and `dest` must be valid for `count` bytes
+///   forward.
+#[inline]
+pub unsafe fn try_copy_u16_forward(
+  dest: *mut mu_u16, src: *const mu_u16, count: usize,
+) -> Result<(), CopyError> {
+  validate_copy_forward(dest as usize, src as usize, count, 2)?;
+  copy_u16_forward(dest, src, count);
+  Ok(())
+}
@@ -0,0 +1,26 @@
+use crate::*;
+
+/// Like [`copy_u8_backward`], but first checks the rules of its safety contract
+/// that can be checked from the pointers alone, and gives an error instead of
+/// copying if any of them are broken.
+///
+/// ## Failure
+/// * [`CopyError::Null`] if either pointer is null.
+/// * [`CopyError::LengthOverflow`] if `count` exceeds `isize::MAX`, or a region
+///   would start below address zero.
+/// * [`CopyError::BadOverlap`] if the regions partly overlap with `src` greater
+///   than `dest`.
+///
+/// If `count` is zero this always succeeds, and nothing is checked or accessed.
+///
+/// ## Safety
+/// * If the checks pass, both `src` and `dest` must be the one-past-the-end
+///   pointers of regions valid for `count` bytes.
+#[inline]
+pub unsafe fn try_copy_u8_backward(
+  dest: *mut mu_u8, src: *const mu_u8, count: usize,
+) -> Result<(), CopyError> {
+  validate_copy_backward(dest as usize, src as usize, count, 1)?;
+  copy_u8_backward(dest, src, count);
+  Ok(())
+}
@@ -1,8 +1,21 @@
 use aeabi_fns::{
-  copy_u16_backward, copy_u16_forward, copy_u32_backward, copy_u32_forward,
-  copy_u8_backward, copy_u8_forward,
+  compare_u16, compare_u32, compare_u8, copy_backward, copy_backward_any,
+  copy_forward, copy_forward_any, copy_u16_backward, copy_u16_backward_advance,
+  copy_u16_backward_const, copy_u16_forward, copy_u16_forward_advance,
+  copy_u16_forward_const, copy_u16_swap_forward, copy_u32_backward,
+  copy_u32_backward_advance, copy_u32_backward_const, copy_u32_forward,
+  copy_u32_forward_advance, copy_u32_forward_const, copy_u32_swap_forward,
+  copy_u64_backward, copy_u64_forward, copy_u8_backward,
+  copy_u8_backward_advance, copy_u8_backward_const, copy_u8_forward,
+  copy_u8_forward_advance, copy_u8_forward_const, copy_u8_forward_replicating,
+  fill_pattern, memchr, memrchr, strlen, swap_u16, swap_u32, swap_u8,
+  try_copy_backward, try_copy_forward,
 };
 
+#[macro_use]
+mod common;
+use common::{CopyFn, SwapFn};
+
 // Note(Lokathor): Different base types on the vecs to get different minimum
 // alignments on the buffer.
 
@@ -27,6 +40,13 @@ fn rand_words(n: usize) -> Vec<u32> {
   v
 }
 
+/// Like [`rand_words`], but the buffer is aligned to 8.
+fn rand_dwords(n: usize) -> Vec<u64> {
+  let mut v = vec![0_u64; n];
+  getrandom::getrandom(bytemuck::cast_slice_mut(&mut v)).unwrap();
+  v
+}
+
 fn rand_u32() -> u32 {
   let mut bytes = [0; 4];
   getrandom::getrandom(&mut bytes).unwrap();
@@ -158,7 +178,7 @@ fn test_copy_u8_backward() {
     }
   }
 
-  // src > dest works even when the regions overlap
+  // src < dest works even when the regions overlap
   for len in 0..=16_usize {
     let src = rand_bytes(64);
     for s in 0..len {
@@ -314,12 +334,13 @@ fn test_copy_u16_backward() {
     }
   }
 
-  // src > dest works even when the regions overlap
+  // src < dest works even when the regions overlap
+  // (the offsets here go down from the end, so `s >= d` means src < dest)
   for len in 0..=16_usize {
     let src = rand_halfwords(64);
     for s in 0..len {
       for d in 0..len {
-        if s > d {
+        if s < d {
           continue;
         }
         let mut out_expected = src.clone();
@@ -477,12 +498,13 @@ fn test_copy_u32_backward() {
     }
   }
 
-  // src > dest works even when the regions overlap
+  // src < dest works even when the regions overlap
+  // (the offsets here go down from the end, so `s >= d` means src < dest)
   for len in 0..=16_usize {
     let src = rand_words(64);
     for s in 0..len {
       for d in 0..len {
-        if s > d {
+        if s < d {
           continue;
         }
         let mut out_expected = src.clone();
@@ -512,3 +534,797 @@ fn test_copy_u32_backward() {
     }
   }
 }
+
+#[test]
+fn test_long_copies() {
+  // The 0..=16 tests above don't reach the 64-byte and 16-byte bulk loops that
+  // some targets use, so here we check longer copies against `core::ptr::copy`.
+  let forward: [(CopyFn, usize); 5] = [
+    (as_copy_fn!(copy_u8_forward), 1),
+    (as_copy_fn!(copy_u16_forward), 2),
+    (as_copy_fn!(copy_u32_forward), 4),
+    (as_copy_fn!(copy_u64_forward), 8),
+    (as_copy_fn!(copy_forward_any), 1),
+  ];
+  let backward: [(CopyFn, usize); 5] = [
+    (as_copy_fn!(copy_u8_backward), 1),
+    (as_copy_fn!(copy_u16_backward), 2),
+    (as_copy_fn!(copy_u32_backward), 4),
+    (as_copy_fn!(copy_u64_backward), 8),
+    (as_copy_fn!(copy_backward_any), 1),
+  ];
+  let len_step = if cfg!(miri) { 7 } else { 1 };
+  for len in (0..=200_usize).step_by(len_step) {
+    // the offsets below are from a base that's aligned to 8
+    let base = rand_dwords(64);
+    for (f, align) in forward {
+      for d in (0..=8).step_by(align) {
+        for s in (0..=8).step_by(align) {
+          // disjoint
+          let mut expected = base.clone();
+          let mut actual = base.clone();
+          unsafe {
+            let p = expected.as_mut_ptr().cast::<u8>();
+            core::ptr::copy(p.add(256 + s), p.add(d), len);
+            let p = actual.as_mut_ptr().cast::<u8>();
+            f(p.add(d), p.add(256 + s), len);
+          }
+          assert_eq!(expected, actual, "len:{len}, d:{d}, s:{s}");
+          // src > dest with overlap
+          let mut expected = base.clone();
+          let mut actual = base.clone();
+          unsafe {
+            let p = expected.as_mut_ptr().cast::<u8>();
+            core::ptr::copy(p.add(16 + s), p.add(d), len);
+            let p = actual.as_mut_ptr().cast::<u8>();
+            f(p.add(d), p.add(16 + s), len);
+          }
+          assert_eq!(expected, actual, "len:{len}, d:{d}, s:{s}");
+        }
+      }
+    }
+    for (f, align) in backward {
+      // the one-past-the-end pointers are the ones that must be aligned
+      for d in (0..=8).step_by(align) {
+        for s in (0..=8).step_by(align) {
+          // disjoint
+          let (d_end, s_end) = (224 + d, 480 + s);
+          let mut expected = base.clone();
+          let mut actual = base.clone();
+          unsafe {
+            let p = expected.as_mut_ptr().cast::<u8>();
+            core::ptr::copy(p.add(s_end - len), p.add(d_end - len), len);
+            let p = actual.as_mut_ptr().cast::<u8>();
+            f(p.add(d_end), p.add(s_end), len);
+          }
+          assert_eq!(expected, actual, "len:{len}, d:{d}, s:{s}");
+          // src < dest with overlap
+          let (d_end, s_end) = (240 + d, 224 + s);
+          let mut expected = base.clone();
+          let mut actual = base.clone();
+          unsafe {
+            let p = expected.as_mut_ptr().cast::<u8>();
+            core::ptr::copy(p.add(s_end - len), p.add(d_end - len), len);
+            let p = actual.as_mut_ptr().cast::<u8>();
+            f(p.add(d_end), p.add(s_end), len);
+          }
+          assert_eq!(expected, actual, "len:{len}, d:{d}, s:{s}");
+        }
+      }
+    }
+  }
+}
+
+/// Checks a backward copy function against `core::ptr::copy` for every length
+/// from 0 to 64, every alignment of the one-past-the-end pointers (mod 8), and
+/// every distance between the regions (overlapping or not) that's allowed.
+fn check_backward_exhaustive(f: CopyFn, align: usize) {
+  let base = rand_dwords(64);
+  let len_step = if cfg!(miri) { 9 } else { 1 };
+  for len in (0..=64_usize).step_by(len_step) {
+    for a in (0..8).step_by(align) {
+      let d_end = 192 + a;
+      // src may be anywhere from fully below dest to fully above it
+      let lowest = d_end - (len + 8).next_multiple_of(align);
+      let highest = d_end + len + 8;
+      for s_end in (lowest..=highest).step_by(align) {
+        // overlapping is only allowed when `src` is less than `dest`
+        if s_end > d_end && s_end - len < d_end {
+          continue;
+        }
+        let mut expected = base.clone();
+        let mut actual = base.clone();
+        unsafe {
+          let p = expected.as_mut_ptr().cast::<u8>();
+          core::ptr::copy(p.add(s_end - len), p.add(d_end - len), len);
+          let p = actual.as_mut_ptr().cast::<u8>();
+          f(p.add(d_end), p.add(s_end), len);
+        }
+        assert_eq!(expected, actual, "len:{len}, d_end:{d_end}, s_end:{s_end}");
+      }
+    }
+  }
+}
+
+#[test]
+fn test_copy_u8_backward_exhaustive() {
+  check_backward_exhaustive(as_copy_fn!(copy_u8_backward), 1);
+}
+
+#[test]
+fn test_copy_u16_backward_exhaustive() {
+  // odd lengths here are what reach the `count & 1` tail
+  check_backward_exhaustive(as_copy_fn!(copy_u16_backward), 2);
+}
+
+#[test]
+fn test_copy_u32_backward_exhaustive() {
+  check_backward_exhaustive(as_copy_fn!(copy_u32_backward), 4);
+}
+
+#[test]
+fn test_copy_u64_backward_exhaustive() {
+  check_backward_exhaustive(as_copy_fn!(copy_u64_backward), 8);
+}
+
+/// Checks a swap function against `core::ptr::swap_nonoverlapping` for every
+/// length from 0 to 64, every alignment of `a` (mod 8), and every position of
+/// `b` from fully below `a` to fully above it that doesn't overlap (or that
+/// equals `a`).
+fn check_swap_exhaustive(f: SwapFn, align: usize) {
+  let base = rand_dwords(64);
+  let len_step = if cfg!(miri) { 9 } else { 1 };
+  for len in (0..=64_usize).step_by(len_step) {
+    for x in (0..8).step_by(align) {
+      let a = 96 + x;
+      let lowest = a - (len + 8).next_multiple_of(align);
+      let highest = a + len + 8;
+      for b in (lowest..=highest).step_by(align) {
+        if b != a && b < a + len && a < b + len {
+          continue;
+        }
+        let mut expected = base.clone();
+        let mut actual = base.clone();
+        unsafe {
+          let p = expected.as_mut_ptr().cast::<u8>();
+          if a != b {
+            core::ptr::swap_nonoverlapping(p.add(a), p.add(b), len);
+          }
+          let p = actual.as_mut_ptr().cast::<u8>();
+          f(p.add(a), p.add(b), len);
+        }
+        assert_eq!(expected, actual, "len:{len}, a:{a}, b:{b}");
+      }
+    }
+  }
+}
+
+#[test]
+fn test_swap_u8_exhaustive() {
+  check_swap_exhaustive(as_swap_fn!(swap_u8), 1);
+}
+
+#[test]
+fn test_swap_u16_exhaustive() {
+  // odd lengths here are what reach the `count & 1` tail
+  check_swap_exhaustive(as_swap_fn!(swap_u16), 2);
+}
+
+#[test]
+fn test_swap_u32_exhaustive() {
+  check_swap_exhaustive(as_swap_fn!(swap_u32), 4);
+}
+
+#[test]
+fn test_advance_copies() {
+  macro_rules! check_advance {
+    ($forward:ident, $backward:ident) => {
+      let mut src = vec![0_u32; 64];
+      getrandom::getrandom(bytemuck::cast_slice_mut(&mut src)).unwrap();
+      for count in 0..=40_usize {
+        let mut dest = vec![0_u32; 64];
+        unsafe {
+          let d = dest.as_mut_ptr().cast::<u8>();
+          let s = src.as_ptr().cast::<u8>();
+          let (d_end, s_end) = $forward(d.cast(), s.cast(), count);
+          assert_eq!(
+            (d_end.cast::<u8>(), s_end.cast::<u8>()),
+            (d.add(count), s.add(count)),
+            "{}: count:{count}",
+            stringify!($forward)
+          );
+          let (d_start, s_start) =
+            $backward(d.add(256).cast(), s.add(256).cast(), count);
+          assert_eq!(
+            (d_start.cast::<u8>(), s_start.cast::<u8>()),
+            (d.add(256 - count), s.add(256 - count)),
+            "{}: count:{count}",
+            stringify!($backward)
+          );
+        }
+        let src_bytes: &[u8] = bytemuck::cast_slice(&src);
+        let dest_bytes: &[u8] = bytemuck::cast_slice(&dest);
+        assert_eq!(dest_bytes[..count], src_bytes[..count]);
+        assert_eq!(dest_bytes[256 - count..], src_bytes[256 - count..]);
+      }
+
+      // copies chained through the returned pointers act like one big copy
+      let mut dest = vec![0_u32; 64];
+      unsafe {
+        let mut d = dest.as_mut_ptr().cast();
+        let mut s = src.as_ptr().cast();
+        for count in [4, 12, 8, 36] {
+          (d, s) = $forward(d, s, count);
+        }
+        let mut d = dest.as_mut_ptr().add(64).cast();
+        let mut s = src.as_ptr().add(64).cast();
+        for count in [8, 4, 36, 12] {
+          (d, s) = $backward(d, s, count);
+        }
+      }
+      let src_bytes: &[u8] = bytemuck::cast_slice(&src);
+      let dest_bytes: &[u8] = bytemuck::cast_slice(&dest);
+      assert_eq!(dest_bytes[..60], src_bytes[..60]);
+      assert!(dest_bytes[60..196].iter().all(|&b| b == 0));
+      assert_eq!(dest_bytes[196..], src_bytes[196..]);
+    };
+  }
+  check_advance!(copy_u8_forward_advance, copy_u8_backward_advance);
+  check_advance!(copy_u16_forward_advance, copy_u16_backward_advance);
+  check_advance!(copy_u32_forward_advance, copy_u32_backward_advance);
+}
+
+#[test]
+fn test_copy_u8_forward_replicating() {
+  let base = rand_words(32);
+  for count in 0..=64_usize {
+    // src below dest repeats the bytes between them, and src at or above dest
+    // is an ordinary forward copy
+    for (d, s) in [(1, 0), (2, 0), (3, 0), (5, 1), (8, 0), (9, 2), (0, 0)]
+      .into_iter()
+      .chain([(0, 3), (4, 64), (64, 4)])
+    {
+      let mut expected = base.clone();
+      let mut actual = base.clone();
+      let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut expected);
+      for i in 0..count {
+        bytes[d + i] = bytes[s + i];
+      }
+      unsafe {
+        let p = actual.as_mut_ptr().cast::<u8>();
+        copy_u8_forward_replicating(p.add(d).cast(), p.add(s).cast(), count);
+      }
+      assert_eq!(expected, actual, "count:{count}, d:{d}, s:{s}");
+    }
+  }
+}
+
+#[test]
+fn test_swap_copies() {
+  // `len` is in elements, and `d` and `s` are where the regions start. Each
+  // `(d, s)` is disjoint, the same place, or `src` above `dest` overlapping.
+  let starts = [(0, 40), (41, 3), (5, 5), (0, 1), (2, 5), (6, 7)];
+  for len in 0..=32_usize {
+    for (d, s) in starts {
+      let base = rand_halfwords(80);
+      let mut expected = base.clone();
+      for i in 0..len {
+        expected[d + i] = base[s + i].swap_bytes();
+      }
+      let mut actual = base.clone();
+      unsafe {
+        let p = actual.as_mut_ptr();
+        copy_u16_swap_forward(p.add(d).cast(), p.add(s), len * 2);
+      }
+      assert_eq!(expected, actual, "len:{len}, d:{d}, s:{s}");
+
+      let base = rand_words(80);
+      let mut expected = base.clone();
+      for i in 0..len {
+        expected[d + i] = base[s + i].swap_bytes();
+      }
+      let mut actual = base.clone();
+      unsafe {
+        let p = actual.as_mut_ptr();
+        copy_u32_swap_forward(p.add(d).cast(), p.add(s), len * 4);
+      }
+      assert_eq!(expected, actual, "len:{len}, d:{d}, s:{s}");
+    }
+  }
+}
+
+#[test]
+fn test_generic_copies() {
+  // A `u64` buffer is aligned enough for every type below, and the offsets
+  // are in units of `T`.
+  macro_rules! check_generic {
+    ($t:ty) => {
+      let size = core::mem::size_of::<$t>().max(1);
+      let mut base = vec![0_u64; 128];
+      getrandom::getrandom(bytemuck::cast_slice_mut(&mut base)).unwrap();
+      let elements = 1024 / size;
+      for count in 0..=20_usize {
+        for (d, s) in [(0, 30), (30, 0), (0, 0), (0, 3), (3, 0), (5, 6)] {
+          let mut expected = base.clone();
+          let mut actual = base.clone();
+          // forward only allows `src` above `dest` when they overlap
+          if s >= d || s + count <= d {
+            unsafe {
+              let p = expected.as_mut_ptr().cast::<$t>();
+              core::ptr::copy(p.add(s), p.add(d), count);
+              let p = actual.as_mut_ptr().cast::<$t>();
+              copy_forward(p.add(d), p.add(s), count);
+            }
+            assert_eq!(
+              expected,
+              actual,
+              "forward {}: count:{count}, d:{d}, s:{s}",
+              stringify!($t)
+            );
+          }
+          // backward only allows `src` below `dest` when they overlap, and it
+          // uses the end of the buffer
+          let (d_end, s_end) = (elements - 30 + d, elements - 30 + s);
+          if s <= d || d + count <= s {
+            unsafe {
+              let p = expected.as_mut_ptr().cast::<$t>();
+              core::ptr::copy(
+                p.add(s_end - count),
+                p.add(d_end - count),
+                count,
+              );
+              let p = actual.as_mut_ptr().cast::<$t>();
+              copy_backward(p.add(d_end), p.add(s_end), count);
+            }
+            assert_eq!(
+              expected,
+              actual,
+              "backward {}: count:{count}, d:{d}, s:{s}",
+              stringify!($t)
+            );
+          }
+        }
+      }
+    };
+  }
+  check_generic!(u8);
+  check_generic!(u16);
+  check_generic!(u32);
+  check_generic!(u64);
+  check_generic!([u8; 3]);
+  check_generic!((u16, u8));
+  check_generic!([u16; 3]);
+  check_generic!(());
+}
+
+#[test]
+fn test_try_copies() {
+  use aeabi_fns::{CopyError, CopyPointer};
+  let mut buf = [0_u32; 16];
+  for (i, x) in buf.iter_mut().enumerate() {
+    *x = i as u32;
+  }
+  let p = buf.as_mut_ptr();
+  let null = core::ptr::null_mut::<u32>();
+  let misaligned = |which| CopyError::Misaligned { which, required: 4 };
+  unsafe {
+    // forward
+    assert_eq!(try_copy_forward(p, p.add(2), 4), Ok(()));
+    assert_eq!(buf[..6], [2, 3, 4, 5, 4, 5]);
+    assert_eq!(try_copy_forward(p.add(2), p, 4), Err(CopyError::BadOverlap));
+    assert_eq!(try_copy_forward(p.add(2), p, 2), Ok(()));
+    assert_eq!(try_copy_forward(null, p, 1), Err(CopyError::Null));
+    assert_eq!(try_copy_forward(p, null, 1), Err(CopyError::Null));
+    assert_eq!(try_copy_forward(null, null, 0), Ok(()));
+    assert_eq!(
+      try_copy_forward(p.byte_add(2), p.add(8), 1),
+      Err(misaligned(CopyPointer::Dest))
+    );
+    assert_eq!(
+      try_copy_forward(p, p.byte_add(34), 1),
+      Err(misaligned(CopyPointer::Src))
+    );
+    assert_eq!(
+      try_copy_forward(p, p.add(8), usize::MAX / 2),
+      Err(CopyError::LengthOverflow)
+    );
+    assert_eq!(
+      try_copy_forward(p, p.add(8), isize::MAX as usize / 4 + 1),
+      Err(CopyError::LengthOverflow)
+    );
+    let high = core::ptr::without_provenance_mut::<u32>(usize::MAX - 7);
+    assert_eq!(try_copy_forward(high, p, 4), Err(CopyError::LengthOverflow));
+
+    // backward
+    let end = p.add(16);
+    assert_eq!(try_copy_backward(end, end.sub(2), 4), Ok(()));
+    assert_eq!(buf[10..], [10, 11, 10, 11, 12, 13]);
+    assert_eq!(
+      try_copy_backward(end.sub(2), end, 4),
+      Err(CopyError::BadOverlap)
+    );
+    assert_eq!(try_copy_backward(end.sub(2), end, 2), Ok(()));
+    assert_eq!(try_copy_backward(null, end, 1), Err(CopyError::Null));
+    assert_eq!(
+      try_copy_backward(end, end.byte_sub(34), 1),
+      Err(misaligned(CopyPointer::Src))
+    );
+    let low = core::ptr::without_provenance_mut::<u32>(8);
+    assert_eq!(try_copy_backward(low, end, 4), Err(CopyError::LengthOverflow));
+
+    // zero-sized types never copy anything
+    let zst = core::ptr::null_mut::<()>();
+    assert_eq!(try_copy_forward(zst, zst, usize::MAX), Ok(()));
+    assert_eq!(try_copy_backward(zst, zst, usize::MAX), Ok(()));
+  }
+}
+
+#[test]
+fn test_try_typed_copies() {
+  use aeabi_fns::{
+    try_copy_u16_backward, try_copy_u16_forward, try_copy_u32_backward,
+    try_copy_u32_forward, try_copy_u8_backward, try_copy_u8_forward, CopyError,
+    CopyPointer,
+  };
+  // words, so that the start is aligned for every size
+  let mut words = [0_u32; 8];
+  let buf: &mut [u8; 32] = bytemuck::cast_mut(&mut words);
+  for (i, x) in buf.iter_mut().enumerate() {
+    *x = i as u8;
+  }
+  let p = buf.as_mut_ptr();
+  let null = core::ptr::null_mut::<u8>();
+  let misaligned = |which, required| CopyError::Misaligned { which, required };
+  unsafe {
+    // u8: any alignment or count is fine
+    assert_eq!(try_copy_u8_forward(p.cast(), p.add(3).cast(), 5), Ok(()));
+    assert_eq!(buf[..8], [3, 4, 5, 6, 7, 5, 6, 7]);
+    assert_eq!(
+      try_copy_u8_forward(p.add(3).cast(), p.cast(), 5),
+      Err(CopyError::BadOverlap)
+    );
+    assert_eq!(
+      try_copy_u8_backward(p.add(32).cast(), p.add(29).cast(), 5),
+      Ok(())
+    );
+    assert_eq!(buf[24..], [24, 25, 26, 24, 25, 26, 27, 28]);
+    assert_eq!(
+      try_copy_u8_backward(p.add(29).cast(), p.add(32).cast(), 5),
+      Err(CopyError::BadOverlap)
+    );
+    assert_eq!(
+      try_copy_u8_forward(null.cast(), p.cast(), 1),
+      Err(CopyError::Null)
+    );
+
+    // u16
+    assert_eq!(try_copy_u16_forward(p.cast(), p.add(8).cast(), 4), Ok(()));
+    assert_eq!(buf[..4], [8, 9, 10, 11]);
+    assert_eq!(
+      try_copy_u16_forward(p.add(1).cast(), p.add(8).cast(), 4),
+      Err(misaligned(CopyPointer::Dest, 2))
+    );
+    assert_eq!(
+      try_copy_u16_backward(p.add(16).cast(), p.add(9).cast(), 4),
+      Err(misaligned(CopyPointer::Src, 2))
+    );
+    // an odd count is fine, it copies the last byte on its own
+    assert_eq!(try_copy_u16_forward(p.cast(), p.add(16).cast(), 3), Ok(()));
+    assert_eq!(buf[..4], [16, 17, 18, 11]);
+    assert_eq!(
+      try_copy_u16_backward(p.add(8).cast(), p.add(24).cast(), 3),
+      Ok(())
+    );
+    assert_eq!(buf[4..8], [7, 21, 22, 23]);
+
+    // u32
+    assert_eq!(
+      try_copy_u32_backward(p.add(32).cast(), p.add(16).cast(), 8),
+      Ok(())
+    );
+    assert_eq!(buf[24..], [8, 9, 10, 11, 12, 13, 14, 15]);
+    assert_eq!(
+      try_copy_u32_forward(p.add(2).cast(), p.add(8).cast(), 4),
+      Err(misaligned(CopyPointer::Dest, 4))
+    );
+    assert_eq!(try_copy_u32_forward(p.cast(), p.add(8).cast(), 6), Ok(()));
+    assert_eq!(buf[..8], [8, 9, 10, 11, 12, 13, 22, 23]);
+    assert_eq!(
+      try_copy_u32_backward(p.add(8).cast(), p.add(16).cast(), 8),
+      Ok(())
+    );
+    assert_eq!(
+      try_copy_u32_backward(p.add(16).cast(), p.add(20).cast(), 8),
+      Err(CopyError::BadOverlap)
+    );
+    assert_eq!(
+      try_copy_u32_forward(p.cast(), p.cast(), usize::MAX),
+      Err(CopyError::LengthOverflow)
+    );
+
+    // nothing is checked for an empty copy
+    assert_eq!(try_copy_u32_forward(null.cast(), p.add(1).cast(), 0), Ok(()));
+  }
+}
+
+#[test]
+fn test_const_copies() {
+  // as a `const` this is definitely evaluated at compile time
+  const SRC: [u32; 3] = [0x0403_0201, 0x0807_0605, 0x0C0B_0A09];
+  const BAKED: [u32; 3] = {
+    let mut a = [0; 3];
+    copy_u32_forward_const(&mut a, &SRC, 10);
+    a
+  };
+  let mut runtime = [0_u32; 3];
+  unsafe {
+    copy_u32_forward(runtime.as_mut_ptr().cast(), SRC.as_ptr().cast(), 10)
+  };
+  assert_eq!(BAKED, runtime);
+
+  // The src is longer than the dest so that the start (forward) and end
+  // (backward) of the slices are at different offsets into it.
+  macro_rules! check_const {
+    (
+      $size:literal, $rand:ident,
+      $fwd:ident, $fwd_const:ident, $bwd:ident, $bwd_const:ident
+    ) => {
+      for len in 0..=8_usize {
+        for count in 0..=(len * $size) {
+          let src = $rand(len + 3);
+          let base = $rand(len);
+
+          let mut expected = base.clone();
+          let mut actual = base.clone();
+          unsafe {
+            $fwd(expected.as_mut_ptr().cast(), src.as_ptr().cast(), count)
+          };
+          $fwd_const(&mut actual, &src, count);
+          assert_eq!(
+            expected,
+            actual,
+            "{}, len:{len}, count:{count}",
+            stringify!($fwd_const)
+          );
+
+          let mut expected = base.clone();
+          let mut actual = base.clone();
+          unsafe {
+            $bwd(
+              expected.as_mut_ptr().add(len).cast(),
+              src.as_ptr().add(len + 3).cast(),
+              count,
+            )
+          };
+          $bwd_const(&mut actual, &src, count);
+          assert_eq!(
+            expected,
+            actual,
+            "{}, len:{len}, count:{count}",
+            stringify!($bwd_const)
+          );
+        }
+      }
+    };
+  }
+  check_const!(
+    1,
+    rand_bytes,
+    copy_u8_forward,
+    copy_u8_forward_const,
+    copy_u8_backward,
+    copy_u8_backward_const
+  );
+  check_const!(
+    2,
+    rand_halfwords,
+    copy_u16_forward,
+    copy_u16_forward_const,
+    copy_u16_backward,
+    copy_u16_backward_const
+  );
+  check_const!(
+    4,
+    rand_words,
+    copy_u32_forward,
+    copy_u32_forward_const,
+    copy_u32_backward,
+    copy_u32_backward_const
+  );
+}
+
+#[test]
+#[should_panic]
+fn test_const_copy_out_of_bounds() {
+  copy_u32_forward_const(&mut [0; 2], &[0; 3], 9);
+}
+
+#[test]
+#[should_panic]
+fn test_const_copy_count_near_max() {
+  copy_u16_backward_const(&mut [0; 2], &[0; 3], usize::MAX);
+}
+
+/// What C's `memcmp` would give for these byte slices.
+fn expected_compare(a: &[u8], b: &[u8]) -> i32 {
+  a.iter()
+    .zip(b)
+    .find(|(x, y)| x != y)
+    .map(|(x, y)| i32::from(*x) - i32::from(*y))
+    .unwrap_or(0)
+}
+
+#[test]
+fn test_compare() {
+  let mut lcg = Lcg::new();
+  type CompareFn = unsafe extern "C" fn(*const u8, *const u8, usize) -> i32;
+  macro_rules! as_compare_fn {
+    ($f:ident) => {
+      unsafe { core::mem::transmute::<*const (), CompareFn>($f as *const ()) }
+    };
+  }
+  let fns: [(CompareFn, usize); 3] = [
+    (as_compare_fn!(compare_u8), 1),
+    (as_compare_fn!(compare_u16), 2),
+    (as_compare_fn!(compare_u32), 4),
+  ];
+  for len in 0..=40_usize {
+    for (f, align) in fns {
+      for a_start in (0..8).step_by(align) {
+        for b_start in (0..8).step_by(align) {
+          let a = rand_words(16);
+          let a_bytes: &[u8] = bytemuck::cast_slice(&a);
+          let a_region = &a_bytes[a_start..a_start + len];
+
+          // random buffers, which will almost always differ at the start
+          let b = rand_words(16);
+          let b_bytes: &[u8] = bytemuck::cast_slice(&b);
+          let b_region = &b_bytes[b_start..b_start + len];
+          let actual = unsafe { f(a_region.as_ptr(), b_region.as_ptr(), len) };
+          assert_eq!(expected_compare(a_region, b_region), actual);
+          assert_eq!(a_region.cmp(b_region), actual.cmp(&0));
+
+          // equal, and near-equal with one byte changed at each position
+          let mut b = vec![0_u32; 16];
+          let b_bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut b);
+          b_bytes[b_start..b_start + len].copy_from_slice(a_region);
+          let actual =
+            unsafe { f(a_region.as_ptr(), b_bytes.as_ptr().add(b_start), len) };
+          assert_eq!(0, actual, "len:{len}");
+          for i in 0..len {
+            let old = b_bytes[b_start + i];
+            let new = loop {
+              let new = lcg.next_u32() as u8;
+              if new != old {
+                break new;
+              }
+            };
+            b_bytes[b_start + i] = new;
+            let b_region = &b_bytes[b_start..b_start + len];
+            let actual =
+              unsafe { f(a_region.as_ptr(), b_region.as_ptr(), len) };
+            assert_eq!(i32::from(a_region[i]) - i32::from(new), actual);
+            assert_eq!(a_region.cmp(b_region), actual.cmp(&0));
+            b_bytes[b_start + i] = old;
+          }
+        }
+      }
+    }
+  }
+}
+
+#[test]
+fn test_memcmp_and_bcmp() {
+  // These pick a routine based on alignment, so we try every pairing.
+  let a = rand_bytes(64);
+  for len in 0..=24_usize {
+    for a_start in 0..8 {
+      for b_start in 0..8 {
+        let mut b = [0_u8; 64];
+        b[b_start..b_start + len].copy_from_slice(&a[a_start..a_start + len]);
+        if len > 0 {
+          b[b_start + len - 1] ^= 0x80;
+        }
+        let a_region = &a[a_start..a_start + len];
+        let b_region = &b[b_start..b_start + len];
+        let expected = expected_compare(a_region, b_region);
+        unsafe {
+          let memcmp =
+            aeabi_fns::memcmp(a_region.as_ptr(), b_region.as_ptr(), len);
+          let bcmp = aeabi_fns::bcmp(a_region.as_ptr(), b_region.as_ptr(), len);
+          assert_eq!(expected, memcmp);
+          assert_eq!(expected != 0, bcmp != 0);
+        }
+      }
+    }
+  }
+}
+
+#[test]
+fn test_strlen() {
+  // try each start alignment with the zero byte at every position after it
+  for start in 0..8 {
+    for len in 0..=40_usize {
+      let mut buf = rand_words(16);
+      let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut buf);
+      for b in &mut bytes[start..start + len] {
+        if *b == 0 {
+          *b = 1;
+        }
+      }
+      bytes[start + len] = 0;
+      let actual = unsafe { strlen(bytes.as_ptr().add(start)) };
+      assert_eq!(len, actual, "start:{start}");
+    }
+  }
+}
+
+#[test]
+fn test_memchr_and_memrchr() {
+  let mut lcg = Lcg::new();
+  for start in 0..8 {
+    for len in 0..=40_usize {
+      let buf = rand_words(16);
+      let bytes: &[u8] = bytemuck::cast_slice(&buf);
+      let region = &bytes[start..start + len];
+      // Look for every byte value that's in the region, and a few random ones
+      // (which usually aren't).
+      let targets = region
+        .iter()
+        .map(|b| i32::from(*b))
+        .chain((0..4).map(|_| lcg.next_u32() as i32));
+      for target in targets {
+        let p = region.as_ptr();
+        let first = region.iter().position(|b| i32::from(*b) == target & 0xFF);
+        let last = region.iter().rposition(|b| i32::from(*b) == target & 0xFF);
+        let expected_first =
+          first.map_or(core::ptr::null(), |i| p.wrapping_add(i));
+        let expected_last =
+          last.map_or(core::ptr::null(), |i| p.wrapping_add(i));
+        unsafe {
+          assert_eq!(expected_first, memchr(p, target, len).cast_const());
+          assert_eq!(expected_last, memrchr(p, target, len).cast_const());
+        }
+      }
+    }
+  }
+}
+
+#[test]
+fn test_fill_pattern() {
+  let pattern: Vec<u8> = (1..=24).collect();
+  let len_step = if cfg!(miri) { 7 } else { 1 };
+  for len in 1..=pattern.len() {
+    let pattern = &pattern[..len];
+    for count in (0..=80_usize).step_by(len_step) {
+      for offset in 0..4 {
+        let mut buf = vec![0_u32; 32];
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut buf);
+        unsafe {
+          fill_pattern(bytes.as_mut_ptr().add(offset).cast(), pattern, count)
+        };
+        let (before, rest) = bytes.split_at(offset);
+        let (filled, after) = rest.split_at(count);
+        assert!(before.iter().all(|&b| b == 0), "len:{len}, count:{count}");
+        assert!(after.iter().all(|&b| b == 0), "len:{len}, count:{count}");
+        for (i, &b) in filled.iter().enumerate() {
+          assert_eq!(
+            b,
+            pattern[i % len],
+            "len:{len}, count:{count}, offset:{offset}, i:{i}"
+          );
+        }
+      }
+    }
+  }
+
+  // with nothing to fill, the pattern and pointer aren't used
+  unsafe { fill_pattern(core::ptr::null_mut(), &[], 0) };
+}
+
+#[test]
+#[should_panic = "`pattern` must not be empty"]
+fn test_fill_pattern_empty() {
+  let mut buf = [0_u8; 4];
+  unsafe { fill_pattern(buf.as_mut_ptr().cast(), &[], 4) };
+}
@@ -0,0 +1,102 @@
+//! Helpers shared by the tests, the copy bench, and the fuzz target (which
+//! include this file with `#[path]`).
+//!
+//! Each user only needs some of these.
+
+#![allow(dead_code, unused_macros)]
+
+/// The copy functions all have this signature once their pointers are cast to
+/// bytes.
+pub type CopyFn = unsafe extern "C" fn(*mut u8, *const u8, usize);
+
+/// Casts one of the crate's copy functions to a [`CopyFn`]. The functions all
+/// have the same ABI, the pointer types are only different to help with
+/// documentation.
+macro_rules! as_copy_fn {
+  ($f:ident) => {
+    unsafe {
+      core::mem::transmute::<*const (), $crate::common::CopyFn>($f as *const ())
+    }
+  };
+}
+
+/// The swap functions all have this signature once their pointers are cast to
+/// bytes.
+pub type SwapFn = unsafe extern "C" fn(*mut u8, *mut u8, usize);
+
+/// Like [`as_copy_fn`], but for the swap functions.
+macro_rules! as_swap_fn {
+  ($f:ident) => {
+    unsafe {
+      core::mem::transmute::<*const (), $crate::common::SwapFn>($f as *const ())
+    }
+  };
+}
+
+/// Where a copy goes within a buffer, after [`fit_copy`] has adjusted it to fit
+/// a function's safety contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FittedCopy {
+  pub buf_len: usize,
+  pub len: usize,
+  /// Where the src region starts.
+  pub s: usize,
+  /// Where the dest region starts.
+  pub d: usize,
+}
+
+/// Adjusts arbitrary copy arguments to fit a function's safety contract.
+///
+/// * `buf_len` is rounded up so that the buffer end is aligned.
+/// * `len` is clamped to `buf_len`.
+/// * `s` and `d` are where the *start* of each region goes, clamped so that the
+///   region fits in the buffer. For forward functions the starts are then
+///   rounded down to `align`, for backward functions the ends are rounded up.
+/// * If the regions overlap in the direction the function doesn't allow, then
+///   `s` and `d` are swapped.
+pub fn fit_copy(
+  buf_len: usize, len: usize, s: usize, d: usize, forward: bool, align: usize,
+) -> FittedCopy {
+  let buf_len = buf_len.next_multiple_of(align);
+  let len = len.min(buf_len);
+  let max_start = buf_len - len;
+  let (mut s, mut d) = (s.min(max_start), d.min(max_start));
+  if forward {
+    s -= s % align;
+    d -= d % align;
+  } else {
+    // rounding up the end can't go past `buf_len`, since it's aligned
+    s = (s + len).next_multiple_of(align) - len;
+    d = (d + len).next_multiple_of(align) - len;
+  }
+  let overlapping = s < d + len && d < s + len;
+  if overlapping && (if forward { s < d } else { s > d }) {
+    core::mem::swap(&mut s, &mut d);
+  }
+  FittedCopy { buf_len, len, s, d }
+}
+
+/// Does a [`fit_copy`] copy within `base` with both `f` and `core::ptr::copy`,
+/// and gives the `(expected, actual)` buffers.
+///
+/// `base` is `u64` so that its address is aligned for every function, and it
+/// must be at least `copy.buf_len` bytes.
+pub fn run_copy(
+  f: CopyFn, forward: bool, copy: FittedCopy, base: Vec<u64>,
+) -> (Vec<u64>, Vec<u64>) {
+  let FittedCopy { buf_len, len, s, d } = copy;
+  assert!(base.len() * 8 >= buf_len);
+  let mut expected = base.clone();
+  let mut actual = base;
+  unsafe {
+    let p = expected.as_mut_ptr().cast::<u8>();
+    core::ptr::copy(p.add(s), p.add(d), len);
+    let p = actual.as_mut_ptr().cast::<u8>();
+    if forward {
+      f(p.add(d), p.add(s), len);
+    } else {
+      f(p.add(d + len), p.add(s + len), len);
+    }
+  }
+  (expected, actual)
+}
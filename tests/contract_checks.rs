@@ -0,0 +1,103 @@
+//! Checks that breaking a copy (or swap) function's safety contract panics with
+//! a message naming the broken rule.
+//!
+//! The copy functions are `extern "C"`, so a panic in them aborts the process.
+//! Because of that, each case runs in a child process (this same test binary,
+//! with an environment variable picking the case), and the parent checks the
+//! child's stderr for the message.
+
+#![cfg(any(debug_assertions, feature = "checked"))]
+
+use aeabi_fns::{
+  copy_backward, copy_backward_any, copy_forward_any, copy_u16_backward,
+  copy_u32_forward, copy_u32_swap_forward, copy_u8_backward, copy_u8_forward,
+  swap_u16, swap_u32,
+};
+use std::process::Command;
+
+const CASE_VAR: &str = "AEABI_FNS_CONTRACT_CASE";
+
+/// Each case's name, and part of the message it should panic with. `{MAX}` is
+/// replaced with `usize::MAX`, which depends on the target.
+const CASES: &[(&str, &str)] = &[
+  ("forward_overlap", "copy_u8_forward: when the regions overlap `src` (0x"),
+  ("backward_overlap", "copy_u8_backward: when the regions overlap `src` (0x"),
+  ("forward_any_overlap", "copy_forward_any: when the regions overlap"),
+  ("backward_any_overlap", "copy_backward_any: when the regions overlap"),
+  ("dest_align", "copy_u32_forward: `dest` (0x"),
+  ("src_align", "copy_u16_backward: `src` (0x"),
+  ("below_zero", "bytes backward, but that goes below address zero"),
+  ("past_end", "bytes forward, but that goes past the end of memory"),
+  ("null", "copy_u16_backward: `dest` (0x0) and `src` (0x"),
+  ("count", "copy_u8_forward: `count` ({MAX}) may not exceed"),
+  ("generic_count", "copy_backward: `count` ({MAX}) may not exceed"),
+  ("swap_overlap", "swap_u16: the regions at `a` (0x"),
+  ("swap_align", "swap_u32: `a` (0x"),
+  ("whole_elements", "copy_u32_swap_forward: `count` (6) must be a multiple"),
+];
+
+/// Makes the call for one case, which should panic.
+fn run_case(case: &str) {
+  let mut buf = [0_u32; 8];
+  let p = buf.as_mut_ptr().cast::<u8>();
+  unsafe {
+    match case {
+      "forward_overlap" => copy_u8_forward(p.add(4).cast(), p.add(2).cast(), 8),
+      "backward_overlap" => {
+        copy_u8_backward(p.add(10).cast(), p.add(12).cast(), 8)
+      }
+      "forward_any_overlap" => {
+        copy_forward_any(p.add(4).cast(), p.add(2).cast(), 8)
+      }
+      "backward_any_overlap" => {
+        copy_backward_any(p.add(10).cast(), p.add(12).cast(), 8)
+      }
+      "dest_align" => copy_u32_forward(p.add(2).cast(), p.add(16).cast(), 8),
+      "src_align" => copy_u16_backward(p.add(30).cast(), p.add(13).cast(), 8),
+      "below_zero" => copy_u8_backward(
+        core::ptr::without_provenance_mut(4),
+        core::ptr::without_provenance(8),
+        16,
+      ),
+      "past_end" => copy_u8_forward(
+        core::ptr::without_provenance_mut(usize::MAX - 2),
+        core::ptr::without_provenance(16),
+        8,
+      ),
+      "null" => copy_u16_backward(core::ptr::null_mut(), p.add(16).cast(), 8),
+      "count" => copy_u8_forward(p.cast(), p.add(16).cast(), usize::MAX),
+      "generic_count" => {
+        copy_backward(p.add(32).cast::<u32>(), p.add(32).cast(), usize::MAX / 2)
+      }
+      "swap_overlap" => swap_u16(p.add(4).cast(), p.add(8).cast(), 8),
+      "swap_align" => swap_u32(p.add(4).cast(), p.add(18).cast(), 8),
+      "whole_elements" => copy_u32_swap_forward(p.cast(), p.add(16).cast(), 6),
+      _ => panic!("unknown case: {case}"),
+    }
+  }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_contract_checks() {
+  if let Ok(case) = std::env::var(CASE_VAR) {
+    run_case(&case);
+    return;
+  }
+  let exe = std::env::current_exe().unwrap();
+  for (case, expected) in CASES {
+    let expected = expected.replace("{MAX}", &usize::MAX.to_string());
+    let output = Command::new(&exe)
+      .args(["test_contract_checks", "--exact", "--nocapture"])
+      .env(CASE_VAR, case)
+      .env("RUST_BACKTRACE", "0")
+      .output()
+      .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "{case}: didn't fail:\n{stderr}");
+    assert!(
+      stderr.contains(&expected),
+      "{case}: expected a panic containing {expected:?}, got:\n{stderr}"
+    );
+  }
+}
@@ -0,0 +1,525 @@
+//! Checks the decompression functions against reference encoders, and with
+//! some hand-made inputs.
+
+use aeabi_fns::{
+  bit_unpack, huff_decompress, huff_decompress_u16, lz77_decompress,
+  lz77_decompress_u16, rl_decompress, rl_decompress_u16, BitUnpackInfo,
+  DecompressError,
+};
+
+/// Makes the 4-byte BIOS header for `kind` and `size`.
+fn header(kind: u8, size: usize) -> Vec<u8> {
+  let size = size as u32;
+  assert!(size < (1 << 24));
+  vec![kind, size as u8, (size >> 8) as u8, (size >> 16) as u8]
+}
+
+/// A simple LZ77 encoder for the BIOS format. At each position it takes the
+/// longest match (the nearest one, if there's a tie) that's at least
+/// `min_distance` back, if that match is at least 3 bytes, and otherwise it
+/// takes a literal byte.
+fn lz77_encode(data: &[u8], min_distance: usize) -> Vec<u8> {
+  let mut out = header(0x10, data.len());
+  let mut pos = 0;
+  while pos < data.len() {
+    let flags_index = out.len();
+    out.push(0);
+    for bit in (0..8).rev() {
+      if pos == data.len() {
+        break;
+      }
+      let (mut best_len, mut best_distance) = (0, 0);
+      for distance in min_distance..=pos.min(4096) {
+        let mut len = 0;
+        // a match can run into the bytes that it's making
+        while len < 18
+          && pos + len < data.len()
+          && data[pos + len] == data[pos + len - distance]
+        {
+          len += 1;
+        }
+        if len > best_len {
+          (best_len, best_distance) = (len, distance);
+        }
+      }
+      if best_len >= 3 {
+        out[flags_index] |= 1 << bit;
+        let (len, distance) = (best_len - 3, best_distance - 1);
+        out.push(((len << 4) | (distance >> 8)) as u8);
+        out.push(distance as u8);
+        pos += best_len;
+      } else {
+        out.push(data[pos]);
+        pos += 1;
+      }
+    }
+  }
+  out
+}
+
+/// A run-length encoder for the BIOS format. Runs of 3 or more of the same byte
+/// become run blocks, and everything else goes in literal blocks.
+fn rl_encode(data: &[u8]) -> Vec<u8> {
+  let mut out = header(0x30, data.len());
+  let mut literals: Vec<u8> = Vec::new();
+  let flush = |out: &mut Vec<u8>, literals: &mut Vec<u8>| {
+    for chunk in literals.chunks(128) {
+      out.push(chunk.len() as u8 - 1);
+      out.extend_from_slice(chunk);
+    }
+    literals.clear();
+  };
+  let mut pos = 0;
+  while pos < data.len() {
+    let run =
+      data[pos..].iter().take(130).take_while(|&&b| b == data[pos]).count();
+    if run >= 3 {
+      flush(&mut out, &mut literals);
+      out.extend_from_slice(&[0x80 | (run - 3) as u8, data[pos]]);
+      pos += run;
+    } else {
+      literals.push(data[pos]);
+      pos += 1;
+    }
+  }
+  flush(&mut out, &mut literals);
+  out
+}
+
+/// A Huffman encoder for the BIOS format, with `bits` sized symbols (4 or 8).
+/// The tree's nodes are laid out breadth first, which keeps the offsets small
+/// enough for up to about 120 different symbols.
+fn huff_encode(data: &[u8], bits: u8) -> Vec<u8> {
+  let symbols: Vec<u8> = if bits == 4 {
+    data.iter().flat_map(|b| [b & 0xF, b >> 4]).collect()
+  } else {
+    data.to_vec()
+  };
+  // Build the tree from the symbol counts, always with at least two leaves.
+  enum Node {
+    Leaf(u8),
+    Branch(Box<Node>, Box<Node>),
+  }
+  let mut counts = [0_usize; 256];
+  for &s in &symbols {
+    counts[usize::from(s)] += 1;
+  }
+  let mut nodes: Vec<(usize, Node)> = (0..=255_u8)
+    .filter(|&s| counts[usize::from(s)] > 0)
+    .map(|s| (counts[usize::from(s)], Node::Leaf(s)))
+    .collect();
+  for s in 0..=1 {
+    if nodes.len() < 2
+      && !nodes.iter().any(|n| matches!(n.1, Node::Leaf(x) if x == s))
+    {
+      nodes.push((0, Node::Leaf(s)));
+    }
+  }
+  while nodes.len() > 1 {
+    nodes.sort_by_key(|n| std::cmp::Reverse(n.0));
+    let (c1, n1) = nodes.pop().unwrap();
+    let (c0, n0) = nodes.pop().unwrap();
+    nodes.push((c0 + c1, Node::Branch(Box::new(n0), Box::new(n1))));
+  }
+  let root = nodes.pop().unwrap().1;
+
+  // Lay out the tree: the root goes at index 1, and the children of the `k`th
+  // branch (in breadth first order) go at indexes `2 * k + 2` and `2 * k + 3`.
+  let mut codes = vec![Vec::new(); 256];
+  let mut tree = vec![0_u8, 0];
+  let mut queue = std::collections::VecDeque::from([(&root, 1, Vec::new())]);
+  let mut branches = 0;
+  while let Some((node, index, code)) = queue.pop_front() {
+    match node {
+      Node::Leaf(s) => {
+        tree[index] = *s;
+        codes[usize::from(*s)] = code;
+      }
+      Node::Branch(n0, n1) => {
+        let child = 2 * branches + 2;
+        branches += 1;
+        let offset = (child - (index & !1) - 2) / 2;
+        assert!(offset < 64, "too many symbols for this encoder");
+        let leaf_flags = u8::from(matches!(**n0, Node::Leaf(_))) << 7
+          | u8::from(matches!(**n1, Node::Leaf(_))) << 6;
+        tree[index] = offset as u8 | leaf_flags;
+        tree.resize(child + 2, 0);
+        for (bit, n) in [(false, n0), (true, n1)] {
+          let mut code = code.clone();
+          code.push(bit);
+          queue.push_back((n, child + usize::from(bit), code));
+        }
+      }
+    }
+  }
+  // The bits need to start at a multiple of 4.
+  if tree.len() % 4 != 0 {
+    tree.resize(tree.len() + 2, 0);
+  }
+  tree[0] = (tree.len() / 2 - 1) as u8;
+
+  let mut out = header(0x20 | bits, data.len());
+  out.extend_from_slice(&tree);
+  let all_bits: Vec<bool> = symbols
+    .iter()
+    .flat_map(|&s| codes[usize::from(s)].iter().copied())
+    .collect();
+  for chunk in all_bits.chunks(32) {
+    let word = chunk
+      .iter()
+      .enumerate()
+      .fold(0_u32, |w, (i, &bit)| w | u32::from(bit) << (31 - i));
+    out.extend_from_slice(&word.to_le_bytes());
+  }
+  out
+}
+
+/// Data of a few kinds that compress differently.
+fn sample_data() -> Vec<Vec<u8>> {
+  let mut rand = vec![0_u8; 1200];
+  getrandom::getrandom(&mut rand).unwrap();
+  let sizes: &[usize] =
+    if cfg!(miri) { &[0, 1, 7, 40] } else { &[0, 1, 2, 3, 17, 255, 256, 1200] };
+  let mut samples = Vec::new();
+  for &size in sizes {
+    // incompressible
+    samples.push(rand[..size].to_vec());
+    // a small alphabet, so there are lots of short matches
+    samples.push(rand[..size].iter().map(|b| b % 3).collect());
+    // long runs of one byte, which need distance 1 (or 2) matches
+    samples.push((0..size).map(|i| rand[i / 20]).collect());
+    // a phrase repeated with a few changes
+    samples.push(
+      (0..size)
+        .map(|i| if i % 97 == 5 { rand[i] } else { b"tile map "[i % 9] })
+        .collect(),
+    );
+  }
+  samples
+}
+
+type Decompress = fn(&[u8], &mut [u8]) -> Result<usize, DecompressError>;
+type DecompressU16 = fn(&[u8], &mut [u16]) -> Result<usize, DecompressError>;
+
+const LZ77: (Decompress, DecompressU16) =
+  (lz77_decompress, lz77_decompress_u16);
+const RL: (Decompress, DecompressU16) = (rl_decompress, rl_decompress_u16);
+const HUFF: (Decompress, DecompressU16) =
+  (huff_decompress, huff_decompress_u16);
+
+/// Decompresses with both functions of a format and checks that they give
+/// `data`.
+fn check_both(
+  (decompress, decompress_u16): (Decompress, DecompressU16), compressed: &[u8],
+  data: &[u8],
+) {
+  let mut dest = vec![0xAA_u8; data.len() + 3];
+  assert_eq!(decompress(compressed, &mut dest), Ok(data.len()));
+  assert_eq!(&dest[..data.len()], data);
+  // the bytes past the size aren't touched
+  assert!(dest[data.len()..].iter().all(|&b| b == 0xAA));
+
+  // with the output starting at both alignments to 4
+  let mut dest16 = vec![0xAAAA_u16; data.len().div_ceil(2) + 2];
+  for start in 0..2 {
+    dest16.fill(0xAAAA);
+    let out = &mut dest16[start..];
+    assert_eq!(decompress_u16(compressed, out), Ok(data.len()));
+    let bytes: &[u8] = bytemuck::cast_slice(out);
+    assert_eq!(&bytes[..data.len()], data);
+    assert!(bytes[data.len()..].iter().all(|&b| b == 0xAA));
+  }
+}
+
+/// Checks the errors that every format has, given the compressed form of
+/// `data`.
+fn check_errors(
+  (decompress, decompress_u16): (Decompress, DecompressU16), compressed: &[u8],
+  data: &[u8],
+) {
+  let mut dest = vec![0; data.len()];
+  let mut dest16 = vec![0; data.len().div_ceil(2)];
+  let mut check = |src: &[u8], expected: DecompressError| {
+    assert_eq!(decompress(src, &mut dest), Err(expected));
+    let expected = match expected {
+      DecompressError::OutputTooSmall { needed } => {
+        DecompressError::OutputTooSmall { needed: needed.div_ceil(2) }
+      }
+      other => other,
+    };
+    assert_eq!(decompress_u16(src, &mut dest16), Err(expected));
+  };
+
+  let mut wrong = compressed.to_vec();
+  wrong[0] = 0x40 | (wrong[0] & 0xF);
+  check(&wrong, DecompressError::WrongFormat { found: wrong[0] });
+  check(&compressed[..3], DecompressError::Truncated);
+  let mut bigger = compressed.to_vec();
+  bigger[1] += 2;
+  check(&bigger, DecompressError::OutputTooSmall { needed: data.len() + 2 });
+}
+
+#[test]
+fn test_lz77_hand_made() {
+  // "abc", then a back-reference of length 9 and distance 3
+  let compressed = [0x10, 12, 0, 0, 0b0001_0000, b'a', b'b', b'c', 0x60, 0x02];
+  check_both(LZ77, &compressed, b"abcabcabcabc");
+
+  // a distance of 1 repeats one byte, which the BIOS's VRAM version can't do
+  let compressed = [0x10, 6, 0, 0, 0b0100_0000, b'z', 0x20, 0x00];
+  check_both(LZ77, &compressed, b"zzzzzz");
+
+  // the last back-reference is cut short at the size
+  let compressed = [0x10, 5, 0, 0, 0b0010_0000, b'a', b'b', 0xF0, 0x01];
+  check_both(LZ77, &compressed, b"ababa");
+
+  // the low 4 bits of the header aren't checked, and unused flags are ignored
+  let compressed = [0x13, 2, 0, 0, 0b0011_1111, b'h', b'i'];
+  check_both(LZ77, &compressed, b"hi");
+
+  // nothing at all
+  check_both(LZ77, &header(0x10, 0), b"");
+}
+
+#[test]
+fn test_lz77_reference_encoder() {
+  for data in sample_data() {
+    check_both(LZ77, &lz77_encode(&data, 1), &data);
+    // like encoders for the BIOS's VRAM version
+    check_both(LZ77, &lz77_encode(&data, 2), &data);
+  }
+}
+
+#[test]
+fn test_lz77_errors() {
+  let data = b"the quick brown fox, the quick brown dog";
+  let compressed = lz77_encode(data, 1);
+  check_errors(LZ77, &compressed, data);
+  for end in 4..compressed.len() {
+    let mut dest = vec![0; data.len()];
+    let result = lz77_decompress(&compressed[..end], &mut dest);
+    assert_eq!(result, Err(DecompressError::Truncated));
+    let mut dest16 = vec![0; data.len().div_ceil(2)];
+    let result = lz77_decompress_u16(&compressed[..end], &mut dest16);
+    assert_eq!(result, Err(DecompressError::Truncated));
+  }
+  // a back-reference at the very start
+  let bad = [0x10, 4, 0, 0, 0x80, 0x10, 0x00];
+  assert_eq!(
+    lz77_decompress(&bad, &mut [0; 4]),
+    Err(DecompressError::BadReference)
+  );
+  assert_eq!(
+    lz77_decompress_u16(&bad, &mut [0; 2]),
+    Err(DecompressError::BadReference)
+  );
+}
+
+#[test]
+fn test_rl_hand_made() {
+  // 3 literal bytes, then a run of 5 bytes
+  let compressed = [0x30, 8, 0, 0, 0x02, b'a', b'b', b'c', 0x82, b'z'];
+  check_both(RL, &compressed, b"abczzzzz");
+
+  // a run of the longest length, cut short at the size
+  let compressed = [0x30, 100, 0, 0, 0xFF, 7];
+  check_both(RL, &compressed, &[7; 100]);
+
+  // a literal block cut short at the size, even though its bytes are missing
+  let compressed = [0x30, 2, 0, 0, 0x7F, b'h', b'i'];
+  check_both(RL, &compressed, b"hi");
+}
+
+#[test]
+fn test_rl_reference_encoder() {
+  for data in sample_data() {
+    check_both(RL, &rl_encode(&data), &data);
+  }
+}
+
+#[test]
+fn test_rl_errors() {
+  let data = b"aaaaaaaaaa, no runs here, bbbbbbbbbbbbbbbbbbbbbbb";
+  let compressed = rl_encode(data);
+  check_errors(RL, &compressed, data);
+  for end in 4..compressed.len() {
+    let mut dest = vec![0; data.len()];
+    let result = rl_decompress(&compressed[..end], &mut dest);
+    assert_eq!(result, Err(DecompressError::Truncated));
+    let mut dest16 = vec![0; data.len().div_ceil(2)];
+    let result = rl_decompress_u16(&compressed[..end], &mut dest16);
+    assert_eq!(result, Err(DecompressError::Truncated));
+  }
+}
+
+#[test]
+fn test_huff_hand_made() {
+  // The tree is: the root (index 1), whose children at 2 and 3 are a leaf for
+  // `a` and a branch, whose children at 4 and 5 are leaves for `b` and `c`. So
+  // the codes are `a` = 0, `b` = 10, and `c` = 11.
+  let tree = [2, 0b1000_0000, b'a', 0b1100_0000, b'b', b'c'];
+  // "abcaab" is 0, 10, 11, 0, 0, 10
+  let bits = 0b0_1011_0010_u32 << (32 - 9);
+  let mut compressed = vec![0x28, 6, 0, 0];
+  compressed.extend_from_slice(&tree);
+  compressed.extend_from_slice(&bits.to_le_bytes());
+  check_both(HUFF, &compressed, b"abcaab");
+
+  // with 4-bit symbols the first one goes in the low half of each byte
+  let tree = [1, 0b1100_0000, 0x3, 0xA];
+  let bits = 0b0101_u32 << 28;
+  let mut compressed = vec![0x24, 2, 0, 0];
+  compressed.extend_from_slice(&tree);
+  compressed.extend_from_slice(&bits.to_le_bytes());
+  check_both(HUFF, &compressed, &[0xA3, 0xA3]);
+}
+
+#[test]
+fn test_huff_reference_encoder() {
+  for data in sample_data() {
+    check_both(HUFF, &huff_encode(&data, 4), &data);
+    // the encoder can't handle every byte value, so narrow them down
+    let data: Vec<u8> = data.iter().map(|b| b % 100).collect();
+    check_both(HUFF, &huff_encode(&data, 8), &data);
+  }
+}
+
+#[test]
+fn test_huff_errors() {
+  let data = b"the quick brown fox jumps over the lazy dog";
+  for bits in [4, 8] {
+    let compressed = huff_encode(data, bits);
+    check_errors(HUFF, &compressed, data);
+    // the tree ends early, or the bits do
+    for end in (4..compressed.len()).step_by(3) {
+      let mut dest = vec![0; data.len()];
+      let result = huff_decompress(&compressed[..end], &mut dest);
+      assert_eq!(result, Err(DecompressError::Truncated));
+      let mut dest16 = vec![0; data.len().div_ceil(2)];
+      let result = huff_decompress_u16(&compressed[..end], &mut dest16);
+      assert_eq!(result, Err(DecompressError::Truncated));
+    }
+  }
+
+  // symbols that aren't 4 or 8 bits
+  let mut compressed = huff_encode(data, 8);
+  compressed[0] = 0x22;
+  let mut dest = vec![0; data.len()];
+  let result = huff_decompress(&compressed, &mut dest);
+  assert_eq!(result, Err(DecompressError::WrongFormat { found: 0x22 }));
+
+  // the root's children past the end of the tree
+  let compressed = [0x28, 1, 0, 0, 1, 0b1100_0001, 0, 0, 0, 0, 0, 0];
+  let result = huff_decompress(&compressed, &mut [0]);
+  assert_eq!(result, Err(DecompressError::BadTree));
+  let result = huff_decompress_u16(&compressed, &mut [0]);
+  assert_eq!(result, Err(DecompressError::BadTree));
+}
+
+/// `bit_unpack` done one unit at a time, with each unit's value OR-ed into its
+/// word at its own position.
+fn bit_unpack_reference(src: &[u8], info: BitUnpackInfo) -> Vec<u32> {
+  let (src_bits, dest_bits) = (info.src_bits, u32::from(info.dest_bits));
+  let offset = info.offset & 0x7FFF_FFFF;
+  let mut values = Vec::new();
+  for &byte in src {
+    for i in 0..8 / src_bits {
+      let unit = u32::from(byte >> (i * src_bits)) & ((1 << src_bits) - 1);
+      values.push(if unit != 0 || info.offset_zeros {
+        unit + offset
+      } else {
+        0
+      });
+    }
+  }
+  // only whole words are stored
+  let per_word = (32 / dest_bits) as usize;
+  values
+    .chunks_exact(per_word)
+    .map(|units| {
+      let mut word = 0_u64;
+      for (i, &value) in units.iter().enumerate() {
+        word |= u64::from(value) << (i as u32 * dest_bits);
+      }
+      word as u32
+    })
+    .collect()
+}
+
+#[test]
+fn test_bit_unpack_hand_made() {
+  let info =
+    BitUnpackInfo { src_bits: 1, dest_bits: 4, offset: 0, offset_zeros: false };
+  let mut dest = [0; 2];
+  assert_eq!(bit_unpack(&[0b1000_0001, 0xFF], &mut dest, info), 2);
+  assert_eq!(dest, [0x1000_0001, 0x1111_1111]);
+
+  // zeroes stay zero unless `offset_zeros` is set
+  let info = BitUnpackInfo { offset: 1, ..info };
+  assert_eq!(bit_unpack(&[0b1000_0001], &mut dest, info), 1);
+  assert_eq!(dest[0], 0x2000_0002);
+  let info = BitUnpackInfo { offset_zeros: true, ..info };
+  assert_eq!(bit_unpack(&[0b1000_0001], &mut dest, info), 1);
+  assert_eq!(dest[0], 0x2111_1112);
+
+  // bit 31 of `offset` is ignored
+  let info = BitUnpackInfo { offset: 0x8000_0001, ..info };
+  assert_eq!(bit_unpack(&[0b1000_0001], &mut dest, info), 1);
+  assert_eq!(dest[0], 0x2111_1112);
+
+  // 0xF + 0xF1 = 0x100 carries out of `dest_bits`, into the bit that the 0xF3
+  // above it already has set
+  let info = BitUnpackInfo {
+    src_bits: 4,
+    dest_bits: 8,
+    offset: 0xF1,
+    offset_zeros: false,
+  };
+  assert_eq!(bit_unpack(&[0x2F, 0x01], &mut dest, info), 1);
+  assert_eq!(dest[0], 0x00F2_F300);
+
+  // a partial word isn't stored
+  dest = [0xAAAA_AAAA; 2];
+  assert_eq!(bit_unpack(&[0x2F], &mut dest, info), 0);
+  assert_eq!(dest, [0xAAAA_AAAA; 2]);
+}
+
+#[test]
+fn test_bit_unpack_reference() {
+  let mut src = [0_u8; 9];
+  getrandom::getrandom(&mut src).unwrap();
+  for src_bits in [1, 2, 4, 8] {
+    for dest_bits in [1, 2, 4, 8, 16, 32] {
+      for offset in [0, 1, 5, 0x7FFF_FFFF, u32::MAX] {
+        for offset_zeros in [false, true] {
+          let info =
+            BitUnpackInfo { src_bits, dest_bits, offset, offset_zeros };
+          for len in 0..=src.len() {
+            let expected = bit_unpack_reference(&src[..len], info);
+            let mut dest = vec![0xAAAA_AAAA; expected.len() + 1];
+            let words = bit_unpack(&src[..len], &mut dest, info);
+            assert_eq!(words, expected.len(), "{info:?}, len:{len}");
+            assert_eq!(&dest[..words], expected, "{info:?}, len:{len}");
+            assert_eq!(dest[words], 0xAAAA_AAAA);
+          }
+        }
+      }
+    }
+  }
+}
+
+#[test]
+#[should_panic = "`dest_bits` must be 1, 2, 4, 8, 16, or 32"]
+fn test_bit_unpack_bad_width() {
+  let info =
+    BitUnpackInfo { src_bits: 1, dest_bits: 3, offset: 0, offset_zeros: false };
+  bit_unpack(&[0], &mut [0; 4], info);
+}
+
+#[test]
+#[should_panic = "`dest` needs room for 2 words"]
+fn test_bit_unpack_short_dest() {
+  let info =
+    BitUnpackInfo { src_bits: 1, dest_bits: 8, offset: 0, offset_zeros: false };
+  bit_unpack(&[0], &mut [0; 1], info);
+}
@@ -0,0 +1,239 @@
+//! Checks that the copy functions never touch memory outside of the region
+//! they're told to copy, and that `strlen` never reads past the zero byte.
+//!
+//! Each buffer is placed right up against an inaccessible guard page, so any
+//! read or write past that edge of the region crashes the test with a
+//! segfault. The rest of the accessible memory around the region is a
+//! poisoned red zone, which is checked after each copy to catch stray writes on
+//! the other side.
+//!
+//! This needs `mmap` and `mprotect`, so it only runs on Linux, and not under
+//! Miri.
+
+#![cfg(all(target_os = "linux", not(miri)))]
+
+use aeabi_fns::{
+  copy_backward_any, copy_forward_any, copy_u16_backward, copy_u16_forward,
+  copy_u32_backward, copy_u32_forward, copy_u64_backward, copy_u64_forward,
+  copy_u8_backward, copy_u8_forward, strlen,
+};
+
+#[macro_use]
+mod common;
+use common::CopyFn;
+
+/// The value of every byte in the red zones.
+const POISON: u8 = 0xA5;
+
+/// Which edge of a [`GuardedBuf`] is against the guard page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+  Low,
+  High,
+}
+
+/// A region of `len` bytes inside an `mmap` allocation.
+///
+/// There's an inaccessible guard page below and above the accessible pages.
+/// The region is `pad` bytes away from the guard page on its `side`, and all
+/// other accessible bytes are set to [`POISON`].
+struct GuardedBuf {
+  map: *mut u8,
+  map_len: usize,
+  page: usize,
+  data: *mut u8,
+  len: usize,
+}
+impl GuardedBuf {
+  fn new(len: usize, side: Side, pad: usize) -> Self {
+    let page = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+    let data_pages = (len + pad).div_ceil(page).max(1);
+    let map_len = (data_pages + 2) * page;
+    unsafe {
+      let map = libc::mmap(
+        core::ptr::null_mut(),
+        map_len,
+        libc::PROT_READ | libc::PROT_WRITE,
+        libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+        -1,
+        0,
+      );
+      assert_ne!(map, libc::MAP_FAILED, "mmap failed");
+      let map = map.cast::<u8>();
+      let high_guard = map.add(map_len - page);
+      for guard in [map, high_guard] {
+        let r = libc::mprotect(guard.cast(), page, libc::PROT_NONE);
+        assert_eq!(r, 0, "mprotect failed");
+      }
+      map.add(page).write_bytes(POISON, data_pages * page);
+      let data = match side {
+        Side::Low => map.add(page + pad),
+        Side::High => high_guard.sub(pad + len),
+      };
+      Self { map, map_len, page, data, len }
+    }
+  }
+
+  fn as_mut_ptr(&mut self) -> *mut u8 {
+    self.data
+  }
+
+  fn data(&self) -> &[u8] {
+    unsafe { core::slice::from_raw_parts(self.data, self.len) }
+  }
+
+  fn data_mut(&mut self) -> &mut [u8] {
+    unsafe { core::slice::from_raw_parts_mut(self.data, self.len) }
+  }
+
+  /// Panics if any byte outside of the region isn't [`POISON`].
+  fn check_red_zones(&self, what: &str) {
+    let accessible = unsafe {
+      core::slice::from_raw_parts(
+        self.map.add(self.page),
+        self.map_len - 2 * self.page,
+      )
+    };
+    let start = self.data as usize - accessible.as_ptr() as usize;
+    let end = start + self.len;
+    for (i, b) in accessible.iter().enumerate() {
+      if (i < start || i >= end) && *b != POISON {
+        panic!(
+          "{what}: red zone byte {i} was written (region is {start}..{end})"
+        );
+      }
+    }
+  }
+}
+impl Drop for GuardedBuf {
+  fn drop(&mut self) {
+    unsafe { libc::munmap(self.map.cast(), self.map_len) };
+  }
+}
+
+/// Fills `bytes` with varied non-[`POISON`] values.
+fn fill(bytes: &mut [u8], seed: u8) {
+  for (i, b) in bytes.iter_mut().enumerate() {
+    let x = (i as u8).wrapping_mul(31).wrapping_add(seed);
+    *b = if x == POISON { !x } else { x };
+  }
+}
+
+/// Runs `f` with regions against the guard page on each side, both with
+/// separate src and dest buffers and with an overlapping copy in one buffer.
+fn check_guarded(f: CopyFn, forward: bool, align: usize, name: &str) {
+  for len in 0..=300_usize {
+    for side in [Side::Low, Side::High] {
+      // Only one end of each region can be aligned, so if the guard page is
+      // at the other end some padding might be needed.
+      let pad_for = |n: usize| {
+        let aligned_side = if forward { Side::Low } else { Side::High };
+        if side == aligned_side {
+          0
+        } else {
+          n.next_multiple_of(align) - n
+        }
+      };
+      let what = format!("{name} len:{len} side:{side:?}");
+
+      // disjoint
+      let mut src = GuardedBuf::new(len, side, pad_for(len));
+      let mut dest = GuardedBuf::new(len, side, pad_for(len));
+      fill(src.data_mut(), 1);
+      fill(dest.data_mut(), 2);
+      unsafe {
+        if forward {
+          f(dest.as_mut_ptr(), src.as_mut_ptr(), len);
+        } else {
+          f(dest.as_mut_ptr().add(len), src.as_mut_ptr().add(len), len);
+        }
+      }
+      assert_eq!(src.data(), dest.data(), "{what}");
+      src.check_red_zones(&what);
+      dest.check_red_zones(&what);
+
+      // overlapping, with the src and dest offset by `align`
+      let total = len + align;
+      let mut buf = GuardedBuf::new(total, side, pad_for(total));
+      fill(buf.data_mut(), 3);
+      let mut expected = buf.data().to_vec();
+      unsafe {
+        let p = buf.as_mut_ptr();
+        if forward {
+          expected.copy_within(align.., 0);
+          f(p, p.add(align), len);
+        } else {
+          expected.copy_within(..len, align);
+          f(p.add(total), p.add(len), len);
+        }
+      }
+      assert_eq!(buf.data(), &expected[..], "{what} (overlapping)");
+      buf.check_red_zones(&what);
+    }
+  }
+}
+
+#[test]
+fn guarded_copy_u8_forward() {
+  check_guarded(as_copy_fn!(copy_u8_forward), true, 1, "copy_u8_forward");
+}
+
+#[test]
+fn guarded_copy_u8_backward() {
+  check_guarded(as_copy_fn!(copy_u8_backward), false, 1, "copy_u8_backward");
+}
+
+#[test]
+fn guarded_copy_u16_forward() {
+  check_guarded(as_copy_fn!(copy_u16_forward), true, 2, "copy_u16_forward");
+}
+
+#[test]
+fn guarded_copy_u16_backward() {
+  check_guarded(as_copy_fn!(copy_u16_backward), false, 2, "copy_u16_backward");
+}
+
+#[test]
+fn guarded_copy_u32_forward() {
+  check_guarded(as_copy_fn!(copy_u32_forward), true, 4, "copy_u32_forward");
+}
+
+#[test]
+fn guarded_copy_u32_backward() {
+  check_guarded(as_copy_fn!(copy_u32_backward), false, 4, "copy_u32_backward");
+}
+
+#[test]
+fn guarded_copy_u64_forward() {
+  check_guarded(as_copy_fn!(copy_u64_forward), true, 8, "copy_u64_forward");
+}
+
+#[test]
+fn guarded_copy_u64_backward() {
+  check_guarded(as_copy_fn!(copy_u64_backward), false, 8, "copy_u64_backward");
+}
+
+#[test]
+fn guarded_copy_forward_any() {
+  check_guarded(as_copy_fn!(copy_forward_any), true, 1, "copy_forward_any");
+}
+
+#[test]
+fn guarded_copy_backward_any() {
+  check_guarded(as_copy_fn!(copy_backward_any), false, 1, "copy_backward_any");
+}
+
+#[test]
+fn guarded_strlen() {
+  // The zero byte is the last byte before the guard page, with the string at
+  // every alignment.
+  for len in 0..=40_usize {
+    let mut buf = GuardedBuf::new(len + 1, Side::High, 0);
+    fill(buf.data_mut(), 4);
+    for b in buf.data_mut().iter_mut().filter(|b| **b == 0) {
+      *b = 1;
+    }
+    buf.data_mut()[len] = 0;
+    assert_eq!(unsafe { strlen(buf.as_mut_ptr()) }, len, "len:{len}");
+  }
+}
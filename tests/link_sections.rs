@@ -0,0 +1,189 @@
+//! Checks that each placement puts every function in the section named with
+//! the right prefix.
+//!
+//! The functions are all `#[inline]`, so they're only codegened in the crate
+//! that uses them. For each placement this builds a small wrapper crate with
+//! Cargo (so that `build.rs` picks the prefix, like it would for a user), which
+//! takes the address of every function, and then looks through the wrapper's
+//! assembly for the `.section` directives.
+//!
+//! With no group feature only the copies should be placed, and the `place_*`
+//! group features should only place the functions in those groups.
+//!
+//! The functions and their groups come from the `PLACED_FUNCTIONS` table that
+//! the `iwram-size` xtask also uses, which is checked against the source.
+
+use std::{fmt::Write, path::Path, process::Command};
+
+#[path = "../xtask/src/placed_functions.rs"]
+mod placed_functions;
+use placed_functions::{placed_functions, PLACED_FUNCTIONS};
+
+#[derive(Clone, Copy)]
+enum Placement<'a> {
+  None,
+  /// A placement feature, and possibly group features.
+  Features(&'a [&'a str]),
+  /// A prefix given to the build script with `AEABI_FNS_LINK_SECTION_PREFIX`.
+  Prefix(&'a str),
+}
+
+/// Builds the wrapper crate and gives its assembly.
+fn build_wrapper(name: &str, placement: Placement<'_>) -> String {
+  let base = Path::new(env!("CARGO_TARGET_TMPDIR")).join("link_sections");
+  let dir = base.join(name);
+  std::fs::create_dir_all(dir.join("src")).unwrap();
+
+  let features = match placement {
+    Placement::Features(features) => format!("{features:?}"),
+    _ => "[]".to_string(),
+  };
+  let manifest = format!(
+    "[package]\n\
+    name = \"{name}\"\n\
+    version = \"0.0.0\"\n\
+    edition = \"2021\"\n\
+    \n\
+    [lib]\n\
+    path = \"src/lib.rs\"\n\
+    \n\
+    [dependencies]\n\
+    aeabi_fns = {{ path = {:?}, features = {features} }}\n\
+    \n\
+    [workspace]\n",
+    env!("CARGO_MANIFEST_DIR")
+  );
+  std::fs::write(dir.join("Cargo.toml"), manifest).unwrap();
+
+  let mut lib_rs = String::from(
+    "#![no_std]\n\
+    #[repr(transparent)]\n\
+    pub struct Ptr(*const ());\n\
+    unsafe impl Sync for Ptr {}\n\
+    #[used]\n\
+    pub static FUNCTIONS: &[Ptr] = &[\n",
+  );
+  for (f, _) in placed_functions() {
+    writeln!(lib_rs, "  Ptr(aeabi_fns::{f} as *const ()),").unwrap();
+  }
+  lib_rs.push_str("];\n");
+  std::fs::write(dir.join("src/lib.rs"), lib_rs).unwrap();
+
+  let asm_path = dir.join("out.s");
+  let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".into());
+  let mut cmd = Command::new(cargo);
+  cmd
+    .args(["rustc", "--quiet", "--lib", "--target-dir"])
+    .arg(base.join("target"))
+    .arg("--")
+    .arg(format!("--emit=asm={}", asm_path.display()))
+    .args(["-C", "codegen-units=1"])
+    .current_dir(&dir)
+    .env_remove("AEABI_FNS_LINK_SECTION_PREFIX");
+  if let Placement::Prefix(prefix) = placement {
+    cmd.env("AEABI_FNS_LINK_SECTION_PREFIX", prefix);
+  }
+  let output = cmd.output().expect("couldn't run cargo");
+  assert!(
+    output.status.success(),
+    "building {name} failed:\n{}",
+    String::from_utf8_lossy(&output.stderr)
+  );
+  std::fs::read_to_string(asm_path).unwrap()
+}
+
+/// Gives the name of every section from the `.section` directives.
+fn section_names(asm: &str) -> Vec<&str> {
+  asm
+    .lines()
+    .filter_map(|line| line.trim_start().strip_prefix(".section"))
+    .filter_map(|rest| rest.trim_start().split(',').next())
+    .map(|name| name.trim_matches('"'))
+    .collect()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_link_sections() {
+  let placements = [
+    ("iwram", Placement::Features(&["link_iwram"]), ".iwram."),
+    ("itcm", Placement::Features(&["link_itcm"]), ".itcm."),
+    ("ramfunc", Placement::Features(&["link_ramfunc"]), ".ramfunc."),
+    ("data", Placement::Features(&["link_data"]), ".data."),
+    // the earliest placement feature wins
+    ("both", Placement::Features(&["link_data", "link_iwram"]), ".iwram."),
+    ("custom", Placement::Prefix(".fast_code."), ".fast_code."),
+  ];
+  let copies = PLACED_FUNCTIONS[0].1;
+  for (name, placement, prefix) in placements {
+    let asm = build_wrapper(name, placement);
+    let sections = section_names(&asm);
+    for (f, _) in placed_functions() {
+      let expected = format!("{prefix}{f}");
+      assert_eq!(
+        sections.contains(&expected.as_str()),
+        copies.contains(&f),
+        "{name}: `{expected}` should only exist for the copies by default, \
+        found: {sections:?}"
+      );
+    }
+  }
+
+  // and with no placement, none of those sections should exist
+  let asm = build_wrapper("none", Placement::None);
+  let sections = section_names(&asm);
+  for (f, _) in placed_functions() {
+    assert!(
+      !sections.iter().any(|s| s.ends_with(&format!(".{f}"))),
+      "none: found a section for `{f}`: {sections:?}"
+    );
+  }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_placement_groups() {
+  for (group, group_fns) in PLACED_FUNCTIONS {
+    let asm = build_wrapper(group, Placement::Features(&["link_iwram", group]));
+    let sections = section_names(&asm);
+    for (f, _) in placed_functions() {
+      let expected = format!(".iwram.{f}");
+      assert_eq!(
+        sections.contains(&expected.as_str()),
+        group_fns.contains(&f),
+        "{group}: `{expected}` should only exist for functions in the group"
+      );
+    }
+  }
+}
+
+#[test]
+fn test_placed_functions_table() {
+  // Each `section_name!("name")` in the source, along with the group from the
+  // `place_<group>` cfg in front of it.
+  let src = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+  let mut in_source = Vec::new();
+  for entry in std::fs::read_dir(src).unwrap() {
+    let text = std::fs::read_to_string(entry.unwrap().path()).unwrap();
+    let mut parts = text.split("section_name!(\"");
+    let mut before = parts.next().unwrap();
+    for part in parts {
+      let name = part.split('"').next().unwrap().to_string();
+      let group = before.rsplit_once("place_").map(|(_, rest)| {
+        let end = rest
+          .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+          .unwrap_or(rest.len());
+        format!("place_{}", &rest[..end])
+      });
+      in_source.push((name, group));
+      before = part;
+    }
+  }
+  in_source.sort();
+
+  let mut in_table: Vec<_> = placed_functions()
+    .map(|(f, group)| (f.to_string(), Some(group.to_string())))
+    .collect();
+  in_table.sort();
+  assert_eq!(in_source, in_table, "`PLACED_FUNCTIONS` is out of date");
+}
@@ -0,0 +1,284 @@
+//! Checks that the optimizer hasn't turned any of the crate's loops back into
+//! calls to `memcpy` and friends, which would be infinite recursion once this
+//! crate is used to provide those symbols.
+//!
+//! The functions are all `#[inline]`, so they're only codegened in the crate
+//! that calls them. For each target in [`TARGETS`], this test builds the
+//! library with `rustc`, then builds a small wrapper crate that calls every
+//! function into an object file, and then checks the object's undefined
+//! symbols for any of the forbidden ones.
+//!
+//! The ARM targets need their standard library installed (`rustup target add
+//! thumbv7em-none-eabi armv7a-none-eabi`). The ARMv4T targets themselves are
+//! tier 3 and would need `-Zbuild-std`, so the `armv4t` asm is built for
+//! ARMv7-A instead, which runs the same ARM-state code.
+
+use std::{
+  fmt::Write,
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+/// Each target (`None` for the host) and the crate features to build with.
+/// Without a feature the ARM targets build the Rust fallback loops, which are
+/// the ones that LLVM could turn into calls.
+const TARGETS: &[(Option<&str>, &[&str])] = &[
+  (None, &[]),
+  (Some("thumbv7em-none-eabi"), &[]),
+  (Some("thumbv7em-none-eabi"), &["thumb2"]),
+  (Some("armv7a-none-eabi"), &[]),
+  (Some("armv7a-none-eabi"), &["armv4t"]),
+];
+
+/// These all take `(dest, src, count)` and return nothing.
+const COPY_FNS: &[&str] = &[
+  "copy_u8_forward",
+  "copy_u8_backward",
+  "copy_u16_forward",
+  "copy_u16_backward",
+  "copy_u32_forward",
+  "copy_u32_backward",
+  "copy_u64_forward",
+  "copy_u64_backward",
+  "copy_u8_forward_replicating",
+  "copy_u16_swap_forward",
+  "copy_u32_swap_forward",
+  "copy_forward_any",
+  "copy_backward_any",
+];
+
+/// These all take `(a, b, count)`, with both pointers `*mut`, and return
+/// nothing.
+const SWAP_FNS: &[&str] = &["swap_u8", "swap_u16", "swap_u32"];
+
+/// These all take `(a, b, count)` and return `i32`.
+const COMPARE_FNS: &[&str] = &["compare_u8", "compare_u16", "compare_u32"];
+
+/// These all take `(src, dest)`, with `dest` as `&mut [u8]` or (for the `_u16`
+/// versions) `&mut [u16]`.
+const DECOMPRESS_FNS: &[&str] = &[
+  "lz77_decompress",
+  "lz77_decompress_u16",
+  "rl_decompress",
+  "rl_decompress_u16",
+  "huff_decompress",
+  "huff_decompress_u16",
+];
+
+const FORBIDDEN: &[&str] = &[
+  "memcpy",
+  "memmove",
+  "memset",
+  "memcmp",
+  "bcmp",
+  "strlen",
+  "memchr",
+  "memrchr",
+  "__aeabi_memcpy",
+  "__aeabi_memcpy4",
+  "__aeabi_memcpy8",
+  "__aeabi_memmove",
+  "__aeabi_memmove4",
+  "__aeabi_memmove8",
+  "__aeabi_memset",
+  "__aeabi_memset4",
+  "__aeabi_memset8",
+  "__aeabi_memclr",
+  "__aeabi_memclr4",
+  "__aeabi_memclr8",
+];
+
+fn rustc(target: Option<&str>, args: &[&str]) {
+  let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+  let mut command = Command::new(rustc);
+  command.args(["--edition", "2021", "-C", "opt-level=3", "-C", "panic=abort"]);
+  if let Some(target) = target {
+    command.args(["--target", target]);
+  }
+  let output = command.args(args).output().expect("couldn't run rustc");
+  assert!(
+    output.status.success(),
+    "rustc failed for {}:\n{}",
+    target.unwrap_or("the host"),
+    String::from_utf8_lossy(&output.stderr)
+  );
+}
+
+/// The symbols in an ELF object file's symbol table, and if each is defined.
+fn elf_symbols(elf: &[u8]) -> Vec<(String, bool)> {
+  assert_eq!(&elf[..4], b"\x7fELF", "not an ELF file");
+  assert_eq!(elf[5], 1, "not a little-endian ELF file");
+  let is_64 = elf[4] == 2;
+  let u16_at =
+    |at: usize| usize::from(u16::from_le_bytes([elf[at], elf[at + 1]]));
+  let u32_at = |at: usize| {
+    u32::from_le_bytes(elf[at..at + 4].try_into().unwrap()) as usize
+  };
+  let word_at = |at: usize| {
+    if is_64 {
+      u64::from_le_bytes(elf[at..at + 8].try_into().unwrap()) as usize
+    } else {
+      u32_at(at)
+    }
+  };
+  let (sh_off, sh_entsize, sh_num) = if is_64 {
+    (word_at(0x28), u16_at(0x3A), u16_at(0x3C))
+  } else {
+    (word_at(0x20), u16_at(0x2E), u16_at(0x30))
+  };
+  // (offset, size, link) of a section
+  let section = |i: usize| {
+    let sh = sh_off + i * sh_entsize;
+    if is_64 {
+      (word_at(sh + 24), word_at(sh + 32), u32_at(sh + 40))
+    } else {
+      (word_at(sh + 16), word_at(sh + 20), u32_at(sh + 24))
+    }
+  };
+  const SHT_SYMTAB: usize = 2;
+  let symtab = (0..sh_num)
+    .find(|&i| u32_at(sh_off + i * sh_entsize + 4) == SHT_SYMTAB)
+    .expect("no symbol table");
+  let (sym_off, sym_size, strtab) = section(symtab);
+  let (str_off, _, _) = section(strtab);
+  let sym_entsize = if is_64 { 24 } else { 16 };
+  (sym_off..sym_off + sym_size)
+    .step_by(sym_entsize)
+    .map(|sym| {
+      let name_start = str_off + u32_at(sym);
+      let name_len = elf[name_start..].iter().position(|&b| b == 0).unwrap();
+      let name = &elf[name_start..name_start + name_len];
+      let shndx = u16_at(sym + if is_64 { 6 } else { 14 });
+      (String::from_utf8_lossy(name).into_owned(), shndx != 0)
+    })
+    .collect()
+}
+
+/// Builds the library and `wrapper_rs` for `target`, and gives the path of the
+/// wrapper's object file.
+fn build(
+  out_dir: &Path, wrapper_rs: &Path, target: Option<&str>, features: &[&str],
+) -> PathBuf {
+  let name = format!("{}-{}", target.unwrap_or("host"), features.join("-"));
+  let target_dir = out_dir.join(name);
+  std::fs::create_dir_all(&target_dir).unwrap();
+  let lib_rs = concat!(env!("CARGO_MANIFEST_DIR"), "/src/lib.rs");
+  let feature_cfgs: Vec<String> =
+    features.iter().map(|f| format!("feature=\"{f}\"")).collect();
+  let mut args = vec![
+    "--crate-type=rlib",
+    "--crate-name=aeabi_fns",
+    "--out-dir",
+    target_dir.to_str().unwrap(),
+    lib_rs,
+  ];
+  for cfg in &feature_cfgs {
+    args.extend(["--cfg", cfg]);
+  }
+  rustc(target, &args);
+
+  let wrapper_o = target_dir.join("wrapper.o");
+  let extern_arg =
+    format!("aeabi_fns={}", target_dir.join("libaeabi_fns.rlib").display());
+  rustc(
+    target,
+    &[
+      "--crate-type=lib",
+      "--crate-name=wrapper",
+      "--emit=obj",
+      "--extern",
+      &extern_arg,
+      "-o",
+      wrapper_o.to_str().unwrap(),
+      wrapper_rs.to_str().unwrap(),
+    ],
+  );
+  wrapper_o
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_no_mem_calls() {
+  let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("no_mem_calls");
+  std::fs::create_dir_all(&out_dir).unwrap();
+
+  let mut wrapper = String::from("#![no_std]\n");
+  for name in COPY_FNS {
+    writeln!(
+      wrapper,
+      "#[no_mangle] pub unsafe extern \"C\" fn check_{name}(\
+       d: *mut u8, s: *const u8, n: usize) {{ \
+       aeabi_fns::{name}(d.cast(), s.cast(), n) }}"
+    )
+    .unwrap();
+  }
+  for name in SWAP_FNS {
+    writeln!(
+      wrapper,
+      "#[no_mangle] pub unsafe extern \"C\" fn check_{name}(\
+       a: *mut u8, b: *mut u8, n: usize) {{ \
+       aeabi_fns::{name}(a.cast(), b.cast(), n) }}"
+    )
+    .unwrap();
+  }
+  for name in COMPARE_FNS {
+    writeln!(
+      wrapper,
+      "#[no_mangle] pub unsafe extern \"C\" fn check_{name}(\
+       a: *const u8, b: *const u8, n: usize) -> i32 {{ \
+       aeabi_fns::{name}(a.cast(), b.cast(), n) }}"
+    )
+    .unwrap();
+  }
+  for name in DECOMPRESS_FNS {
+    let unit = if name.ends_with("_u16") { "u16" } else { "u8" };
+    writeln!(
+      wrapper,
+      "#[no_mangle] pub fn check_{name}(s: &[u8], d: &mut [{unit}]) \
+       -> Result<usize, aeabi_fns::DecompressError> {{ \
+       aeabi_fns::{name}(s, d) }}"
+    )
+    .unwrap();
+  }
+  wrapper.push_str(
+    "#[no_mangle] pub unsafe extern \"C\" fn check_strlen(p: *const u8) \
+     -> usize { aeabi_fns::strlen(p) }\n\
+     #[no_mangle] pub unsafe extern \"C\" fn check_memchr(\
+     p: *const u8, c: i32, n: usize) -> *mut u8 { \
+     aeabi_fns::memchr(p, c, n) }\n\
+     #[no_mangle] pub unsafe extern \"C\" fn check_memrchr(\
+     p: *const u8, c: i32, n: usize) -> *mut u8 { \
+     aeabi_fns::memrchr(p, c, n) }\n\
+     #[no_mangle] pub unsafe extern \"C\" fn check_fill_pattern(\
+     d: *mut u8, p: *const u8, len: usize, n: usize) { \
+     let pattern = core::slice::from_raw_parts(p, len); \
+     aeabi_fns::fill_pattern(d.cast(), pattern, n) }\n\
+     #[no_mangle] pub fn check_bit_unpack(\
+     s: &[u8], d: &mut [u32], info: aeabi_fns::BitUnpackInfo) -> usize { \
+     aeabi_fns::bit_unpack(s, d, info) }\n",
+  );
+  let wrapper_rs = out_dir.join("wrapper.rs");
+  std::fs::write(&wrapper_rs, wrapper).unwrap();
+
+  let other_fns = ["strlen", "memchr", "memrchr", "fill_pattern", "bit_unpack"];
+  for &(target, features) in TARGETS {
+    let config = format!("{} {features:?}", target.unwrap_or("host"));
+    let wrapper_o = build(&out_dir, &wrapper_rs, target, features);
+    let symbols = elf_symbols(&std::fs::read(wrapper_o).unwrap());
+    let all_fns =
+      COPY_FNS.iter().chain(SWAP_FNS).chain(COMPARE_FNS).chain(DECOMPRESS_FNS);
+    for name in all_fns.chain(&other_fns) {
+      let check_name = format!("check_{name}");
+      assert!(
+        symbols.iter().any(|(s, defined)| *s == check_name && *defined),
+        "{config}: {name} wasn't emitted"
+      );
+    }
+    for (symbol, defined) in &symbols {
+      assert!(
+        *defined || !FORBIDDEN.contains(&symbol.as_str()),
+        "{config}: the wrapper calls `{symbol}`"
+      );
+    }
+  }
+}
@@ -0,0 +1,87 @@
+//! Property tests for the copy functions.
+//!
+//! Each case is a buffer size, a copy length, and src/dest positions within the
+//! buffer. The positions are then adjusted to fit the function's safety
+//! contract (alignment and which way an overlap is allowed to go), and the
+//! result is compared against `core::ptr::copy` on a clone of the buffer.
+
+use aeabi_fns::{
+  copy_backward_any, copy_forward_any, copy_u16_backward, copy_u16_forward,
+  copy_u32_backward, copy_u32_forward, copy_u64_backward, copy_u64_forward,
+  copy_u8_backward, copy_u8_forward,
+};
+use proptest::prelude::*;
+
+#[macro_use]
+mod common;
+use common::{fit_copy, run_copy, CopyFn};
+
+/// The generated part of a test case, before it's adjusted to fit a function.
+#[derive(Debug, Clone, Copy)]
+struct Case {
+  buf_len: usize,
+  len: usize,
+  s: usize,
+  d: usize,
+  seed: u64,
+}
+
+/// Runs one copy with both `f` and `core::ptr::copy` and compares the buffers,
+/// after [`fit_copy`] adjusts the case to fit `f`'s contract.
+fn check_copy(
+  f: CopyFn, forward: bool, align: usize, case: Case,
+) -> Result<(), TestCaseError> {
+  let Case { buf_len, len, s, d, seed } = case;
+  let copy = fit_copy(buf_len, len, s, d, forward, align);
+  let base: Vec<u64> = (0..copy.buf_len.div_ceil(8))
+    .map(|i| seed.wrapping_add(i as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+    .collect();
+  let (expected, actual) = run_copy(f, forward, copy, base);
+  prop_assert_eq!(
+    expected,
+    actual,
+    "forward:{}, align:{}, {:?}",
+    forward,
+    align,
+    copy
+  );
+  Ok(())
+}
+
+fn config() -> ProptestConfig {
+  ProptestConfig::with_cases(if cfg!(miri) { 16 } else { 1024 })
+}
+
+macro_rules! copy_properties {
+  ($($name:ident: $f:ident, $forward:literal, $align:literal;)*) => {
+    proptest! {
+      #![proptest_config(config())]
+      $(
+        #[test]
+        fn $name(
+          buf_len in 0_usize..1024,
+          len in 0_usize..1024,
+          s in 0_usize..1024,
+          d in 0_usize..1024,
+          seed in any::<u64>(),
+        ) {
+          let case = Case { buf_len, len, s, d, seed };
+          check_copy(as_copy_fn!($f), $forward, $align, case)?;
+        }
+      )*
+    }
+  };
+}
+
+copy_properties! {
+  prop_copy_u8_forward: copy_u8_forward, true, 1;
+  prop_copy_u8_backward: copy_u8_backward, false, 1;
+  prop_copy_u16_forward: copy_u16_forward, true, 2;
+  prop_copy_u16_backward: copy_u16_backward, false, 2;
+  prop_copy_u32_forward: copy_u32_forward, true, 4;
+  prop_copy_u32_backward: copy_u32_backward, false, 4;
+  prop_copy_u64_forward: copy_u64_forward, true, 8;
+  prop_copy_u64_backward: copy_u64_backward, false, 8;
+  prop_copy_forward_any: copy_forward_any, true, 1;
+  prop_copy_backward_any: copy_backward_any, false, 1;
+}
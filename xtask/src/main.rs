@@ -0,0 +1,298 @@
+//! Development tasks for `aeabi_fns`, run with `cargo xtask <task>`.
+//!
+//! Tasks:
+//! * `iwram-size [--budget <file>]`: Builds every placeable function for the
+//!   `armv4t-none-eabi` and `thumbv4t-none-eabi` targets (with the `armv4t`,
+//!   `link_iwram`, and every `place_*` feature) and reports the size of each
+//!   `.iwram.*` section. Fails if a section, a group of sections, or all of
+//!   them together, is over the budget. The default budget file is
+//!   `xtask/iwram_budget.txt`. This needs a Nightly toolchain with the
+//!   `rust-src` component, since it uses `-Zbuild-std`.
+
+mod placed_functions;
+
+use object::{Object, ObjectSection, ObjectSymbol, SectionIndex};
+use std::{
+  collections::BTreeMap,
+  fs,
+  path::{Path, PathBuf},
+  process::{Command, ExitCode},
+};
+
+use placed_functions::{placed_functions, PLACED_FUNCTIONS};
+
+const TARGETS: &[&str] = &["armv4t-none-eabi", "thumbv4t-none-eabi"];
+
+const SECTION_PREFIX: &str = ".iwram.";
+
+fn main() -> ExitCode {
+  let args: Vec<String> = std::env::args().skip(1).collect();
+  let result = match args.first().map(String::as_str) {
+    Some("iwram-size") => iwram_size(&args[1..]),
+    _ => Err("usage: cargo xtask iwram-size [--budget <file>]".to_string()),
+  };
+  match result {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(msg) => {
+      eprintln!("error: {msg}");
+      ExitCode::FAILURE
+    }
+  }
+}
+
+fn root_dir() -> PathBuf {
+  Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+/// The size of one section, split by the ARM mapping symbols.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct SectionSize {
+  bytes: u64,
+  /// Bytes of `a32` code (4 bytes per instruction).
+  arm_bytes: u64,
+  /// Bytes of `t32` code (2 bytes per instruction on ARMv4T).
+  thumb_bytes: u64,
+  /// Bytes of data (literal pools).
+  data_bytes: u64,
+}
+impl SectionSize {
+  fn instructions(&self) -> u64 {
+    self.arm_bytes / 4 + self.thumb_bytes / 2
+  }
+}
+
+fn iwram_size(args: &[String]) -> Result<(), String> {
+  let budget_path = match args {
+    [] => root_dir().join("xtask/iwram_budget.txt"),
+    [flag, path] if flag == "--budget" => PathBuf::from(path),
+    _ => return Err(format!("unexpected arguments: {args:?}")),
+  };
+  let budget = read_budget(&budget_path)?;
+
+  let mut over_budget = Vec::new();
+  for target in TARGETS {
+    let rlib = build_wrapper(target)?;
+    let sizes = section_sizes(&rlib)?;
+    for (f, _) in placed_functions() {
+      if !sizes.contains_key(f) {
+        return Err(format!(
+          "{target}: no `{SECTION_PREFIX}{f}` section found"
+        ));
+      }
+    }
+
+    println!("{target}:");
+    println!(
+      "  {:<26} {:>6} {:>6} {:>6} {:>6}",
+      "section", "bytes", "instrs", "data", "budget"
+    );
+    let mut total = 0;
+    let mut group_totals: BTreeMap<String, u64> = BTreeMap::new();
+    for (name, size) in &sizes {
+      total += size.bytes;
+      let Some((_, group)) = placed_functions().find(|(f, _)| f == name) else {
+        return Err(format!(
+          "{target}: `{SECTION_PREFIX}{name}` isn't in `PLACED_FUNCTIONS`"
+        ));
+      };
+      *group_totals.entry(group.to_string()).or_default() += size.bytes;
+      let limit = budget.get(name.as_str()).copied();
+      println!(
+        "  {:<26} {:>6} {:>6} {:>6} {:>6}",
+        name,
+        size.bytes,
+        size.instructions(),
+        size.data_bytes,
+        limit.map_or("-".to_string(), |b| b.to_string())
+      );
+      check_limit(&mut over_budget, target, name, size.bytes, limit);
+    }
+    group_totals.insert("total".to_string(), total);
+    for (name, bytes) in &group_totals {
+      let limit = budget.get(name.as_str()).copied();
+      println!(
+        "  {:<26} {:>6} {:>6} {:>6} {:>6}",
+        name,
+        bytes,
+        "",
+        "",
+        limit.map_or("-".to_string(), |b| b.to_string())
+      );
+      check_limit(&mut over_budget, target, name, *bytes, limit);
+    }
+  }
+
+  if over_budget.is_empty() {
+    Ok(())
+  } else {
+    Err(format!("over the IWRAM budget:\n  {}", over_budget.join("\n  ")))
+  }
+}
+
+fn check_limit(
+  over_budget: &mut Vec<String>, target: &str, name: &str, bytes: u64,
+  limit: Option<u64>,
+) {
+  if let Some(limit) = limit {
+    if bytes > limit {
+      over_budget.push(format!("{target}: {name} is {bytes} > {limit}"));
+    }
+  }
+}
+
+/// Reads lines of `<name> <max bytes>`. The name is a section without the
+/// `.iwram.` prefix, `place_<group>` for all of the sections in a group, or
+/// `total` for all sections together. Blank lines and `#` comments are
+/// skipped.
+fn read_budget(path: &Path) -> Result<BTreeMap<String, u64>, String> {
+  let text = fs::read_to_string(path)
+    .map_err(|e| format!("couldn't read {}: {e}", path.display()))?;
+  let mut budget = BTreeMap::new();
+  for (i, line) in text.lines().enumerate() {
+    let line = line.split('#').next().unwrap().trim();
+    if line.is_empty() {
+      continue;
+    }
+    let mut words = line.split_whitespace();
+    let (Some(name), Some(bytes), None) =
+      (words.next(), words.next(), words.next())
+    else {
+      return Err(format!(
+        "{}:{}: expected `<name> <bytes>`",
+        path.display(),
+        i + 1
+      ));
+    };
+    let bytes = bytes
+      .parse()
+      .map_err(|e| format!("{}:{}: {e}", path.display(), i + 1))?;
+    budget.insert(name.to_string(), bytes);
+  }
+  Ok(budget)
+}
+
+/// Builds a crate that takes the address of every function, so that they're
+/// all codegened (`#[inline]` functions otherwise wouldn't be), and gives the
+/// path of its rlib.
+fn build_wrapper(target: &str) -> Result<PathBuf, String> {
+  let dir = root_dir().join("target/xtask/iwram_size");
+  fs::create_dir_all(dir.join("src")).map_err(|e| e.to_string())?;
+  // only the copies are placed by default, so every group is asked for
+  let mut features = vec!["armv4t", "link_iwram"];
+  features.extend(PLACED_FUNCTIONS.iter().map(|(group, _)| group));
+  let manifest = format!(
+    "[package]\n\
+    name = \"iwram_size\"\n\
+    version = \"0.0.0\"\n\
+    edition = \"2021\"\n\
+    \n\
+    [dependencies]\n\
+    aeabi_fns = {{ path = {:?}, features = {features:?} }}\n\
+    \n\
+    [profile.release]\n\
+    codegen-units = 1\n\
+    \n\
+    [workspace]\n",
+    root_dir().display().to_string()
+  );
+  let mut lib_rs = String::from(
+    "#![no_std]\n\
+    #[repr(transparent)]\n\
+    pub struct Ptr(*const ());\n\
+    unsafe impl Sync for Ptr {}\n\
+    #[used]\n\
+    pub static FUNCTIONS: &[Ptr] = &[\n",
+  );
+  for (f, _) in placed_functions() {
+    lib_rs.push_str(&format!("  Ptr(aeabi_fns::{f} as *const ()),\n"));
+  }
+  lib_rs.push_str("];\n");
+  fs::write(dir.join("Cargo.toml"), manifest).map_err(|e| e.to_string())?;
+  fs::write(dir.join("src/lib.rs"), lib_rs).map_err(|e| e.to_string())?;
+
+  eprintln!("building for {target}...");
+  let output = Command::new("cargo")
+    .args(["+nightly", "build", "--release", "-Zbuild-std=core", "--target"])
+    .arg(target)
+    .current_dir(&dir)
+    // don't let the outer `cargo run` pick the toolchain or target dir
+    .env_remove("RUSTUP_TOOLCHAIN")
+    .env_remove("CARGO_TARGET_DIR")
+    .env_remove("RUSTC")
+    .output()
+    .map_err(|e| format!("couldn't run cargo: {e}"))?;
+  if !output.status.success() {
+    // the crate's warnings are only worth showing if the build failed
+    eprintln!("{}", String::from_utf8_lossy(&output.stderr));
+    return Err(format!("building for {target} failed"));
+  }
+  Ok(dir.join(format!("target/{target}/release/libiwram_size.rlib")))
+}
+
+/// Finds every `.iwram.*` section in the object files of an rlib, keyed by the
+/// name after the prefix.
+fn section_sizes(rlib: &Path) -> Result<BTreeMap<String, SectionSize>, String> {
+  let data = fs::read(rlib)
+    .map_err(|e| format!("couldn't read {}: {e}", rlib.display()))?;
+  let archive = object::read::archive::ArchiveFile::parse(&*data)
+    .map_err(|e| e.to_string())?;
+  let mut sizes: BTreeMap<String, SectionSize> = BTreeMap::new();
+  for member in archive.members() {
+    let member = member.map_err(|e| e.to_string())?;
+    if !member.name().ends_with(b".o") {
+      continue;
+    }
+    let file =
+      object::File::parse(member.data(&*data).map_err(|e| e.to_string())?)
+        .map_err(|e| e.to_string())?;
+    for section in file.sections() {
+      let Some(name) =
+        section.name().ok().and_then(|n| n.strip_prefix(SECTION_PREFIX))
+      else {
+        continue;
+      };
+      let size =
+        split_by_mapping_symbols(&file, section.index(), section.size());
+      let entry = sizes.entry(name.to_string()).or_default();
+      entry.bytes += size.bytes;
+      entry.arm_bytes += size.arm_bytes;
+      entry.thumb_bytes += size.thumb_bytes;
+      entry.data_bytes += size.data_bytes;
+    }
+  }
+  Ok(sizes)
+}
+
+/// ARM ELF files mark where each kind of content starts within a section with
+/// the `$a` (ARM code), `$t` (Thumb code), and `$d` (data) symbols.
+fn split_by_mapping_symbols(
+  file: &object::File, index: SectionIndex, bytes: u64,
+) -> SectionSize {
+  let mut marks: Vec<(u64, u8)> = file
+    .symbols()
+    .filter(|sym| sym.section_index() == Some(index))
+    .filter_map(|sym| {
+      let name = sym.name_bytes().ok()?;
+      match name {
+        [b'$', kind @ (b'a' | b't' | b'd'), rest @ ..]
+          if rest.is_empty() || rest[0] == b'.' =>
+        {
+          Some((sym.address(), *kind))
+        }
+        _ => None,
+      }
+    })
+    .collect();
+  marks.sort_unstable();
+  let mut size = SectionSize { bytes, ..SectionSize::default() };
+  for (i, &(start, kind)) in marks.iter().enumerate() {
+    let end = marks.get(i + 1).map_or(bytes, |&(next, _)| next);
+    let len = end - start;
+    match kind {
+      b'a' => size.arm_bytes += len,
+      b't' => size.thumb_bytes += len,
+      _ => size.data_bytes += len,
+    }
+  }
+  size
+}
@@ -0,0 +1,52 @@
+//! Every function that can be placed, by the feature of its placement group.
+//!
+//! This is shared with `tests/link_sections.rs` (which includes this file with
+//! `#[path]`), and that test checks that it matches the `section_name!` uses in
+//! the crate's source.
+
+pub const PLACED_FUNCTIONS: &[(&str, &[&str])] = &[
+  (
+    "place_copies",
+    &[
+      "copy_u8_forward",
+      "copy_u8_backward",
+      "copy_u16_forward",
+      "copy_u16_backward",
+      "copy_u32_forward",
+      "copy_u32_backward",
+      "copy_u64_forward",
+      "copy_u64_backward",
+      "copy_u8_forward_replicating",
+      "swap_u8",
+      "swap_u16",
+      "swap_u32",
+      "copy_u16_swap_forward",
+      "copy_u32_swap_forward",
+      "copy_forward_any",
+      "copy_backward_any",
+    ],
+  ),
+  ("place_compares", &["compare_u8", "compare_u16", "compare_u32", "memcmp"]),
+  ("place_strings", &["strlen", "memchr", "memrchr"]),
+  ("place_fills", &["fill_pattern"]),
+  (
+    "place_decoders",
+    &[
+      "lz77_decompress",
+      "lz77_decompress_u16",
+      "rl_decompress",
+      "rl_decompress_u16",
+      "huff_decompress",
+      "huff_decompress_u16",
+      "bit_unpack",
+    ],
+  ),
+];
+
+/// Each placed function, along with the feature of its group.
+pub fn placed_functions() -> impl Iterator<Item = (&'static str, &'static str)>
+{
+  PLACED_FUNCTIONS
+    .iter()
+    .flat_map(|&(group, fns)| fns.iter().map(move |&f| (f, group)))
+}